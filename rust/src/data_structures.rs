@@ -1,6 +1,7 @@
 use crate::router::RouterId;
 use serde::{Deserialize, Serialize};
 use std::any::Any;
+use std::net::{AddrParseError, IpAddr, Ipv4Addr, Ipv6Addr};
 use std::ops::{Deref, DerefMut};
 use uuid::Uuid;
 
@@ -36,9 +37,38 @@ impl AppData {
 #[derive(Debug, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TransportListenIp {
-    pub ip: String,
+    pub ip: IpAddr,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub announced_ip: Option<String>,
+    pub announced_ip: Option<IpAddr>,
+}
+
+impl From<Ipv4Addr> for TransportListenIp {
+    fn from(ip: Ipv4Addr) -> Self {
+        Self {
+            ip: IpAddr::V4(ip),
+            announced_ip: None,
+        }
+    }
+}
+
+impl From<Ipv6Addr> for TransportListenIp {
+    fn from(ip: Ipv6Addr) -> Self {
+        Self {
+            ip: IpAddr::V6(ip),
+            announced_ip: None,
+        }
+    }
+}
+
+impl TransportListenIp {
+    /// Parse `ip` from its textual form, failing at the Rust boundary rather than forwarding an
+    /// invalid address string to the worker.
+    pub fn new(ip: &str) -> Result<Self, AddrParseError> {
+        Ok(Self {
+            ip: ip.parse()?,
+            announced_ip: None,
+        })
+    }
 }
 
 #[derive(Debug, Serialize, Copy, Clone)]
@@ -102,7 +132,7 @@ pub enum TransportProtocol {
 pub struct IceCandidate {
     pub foundation: String,
     pub priority: u32,
-    pub ip: String,
+    pub ip: IpAddr,
     pub protocol: TransportProtocol,
     pub port: u16,
     pub r#type: IceCandidateType,
@@ -123,17 +153,14 @@ pub enum IceState {
 #[serde(rename_all = "camelCase", untagged)]
 pub enum TransportTuple {
     LocalOnly {
-        // TODO: Maybe better type for IP address?
-        local_ip: String,
+        local_ip: IpAddr,
         local_port: u16,
         protocol: TransportProtocol,
     },
     WithRemote {
-        // TODO: Maybe better type for IP address?
-        local_ip: String,
+        local_ip: IpAddr,
         local_port: u16,
-        // TODO: Maybe better type for IP address?
-        remote_ip: String,
+        remote_ip: IpAddr,
         remote_port: u16,
         protocol: TransportProtocol,
     },
@@ -174,6 +201,27 @@ pub enum SctpState {
     Closed,
 }
 
+/// Parameters of a QUIC-based data transport, the unordered/partially-reliable alternative to
+/// SCTP-over-DTLS: a `rustls`-backed QUIC endpoint authenticated the same way DTLS is, via
+/// certificate fingerprints, but carrying application data over per-stream QUIC streams instead
+/// of a single SCTP association, so one blocked stream can't head-of-line-block the others.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuicParameters {
+    pub role: DtlsRole,
+    pub fingerprints: Vec<DtlsFingerprint>,
+}
+
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum QuicState {
+    New,
+    Connecting,
+    Connected,
+    Failed,
+    Closed,
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct RouterCreateAudioLevelObserverInternal {
@@ -1,24 +1,64 @@
 use crate::rtp_parameters::{
-    MediaKind, MimeType, MimeTypeVideo, RtcpFeedback, RtcpParameters, RtpCapabilities,
-    RtpCapabilitiesFinalized, RtpCodecCapability, RtpCodecCapabilityFinalized, RtpCodecParameters,
-    RtpCodecParametersParameters, RtpCodecParametersParametersValue, RtpEncodingParameters,
-    RtpEncodingParametersRtx, RtpHeaderExtensionDirection, RtpHeaderExtensionParameters,
-    RtpHeaderExtensionUri, RtpParameters,
+    MediaKind, MimeType, MimeTypeAudio, MimeTypeVideo, RtcpFeedback, RtcpParameters,
+    RtpCapabilities, RtpCapabilitiesFinalized, RtpCodecCapability, RtpCodecCapabilityFinalized,
+    RtpCodecParameters, RtpCodecParametersParameters, RtpCodecParametersParametersValue,
+    RtpEncodingParameters, RtpEncodingParametersRtx, RtpHeaderExtension,
+    RtpHeaderExtensionDirection, RtpHeaderExtensionParameters, RtpHeaderExtensionUri,
+    RtpParameters,
 };
 use crate::scalability_modes::ScalabilityMode;
 use crate::supported_rtp_capabilities;
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::mem;
 use std::num::{NonZeroU32, NonZeroU8};
-use std::ops::Deref;
+use std::ops::{Deref, RangeInclusive};
 use thiserror::Error;
 
-const DYNAMIC_PAYLOAD_TYPES: &[u8] = &[
-    100, 101, 102, 103, 104, 105, 106, 107, 108, 109, 110, 111, 112, 113, 114, 115, 116, 117, 118,
-    119, 120, 121, 122, 123, 124, 125, 126, 127, 96, 97, 98, 99,
-];
+/// Default `profile-level-id` (Constrained Baseline, level 3.1) assumed per RFC 6184 when a
+/// H.264 codec does not declare one.
+const H264_DEFAULT_PROFILE_LEVEL_ID: &str = "42001f";
+
+/// Dynamic payload type ranges to allocate from, in order of preference. The upper range is the
+/// usual RTP dynamic range; the lower one is a valid fallback per RFC 3551/5761 once it is
+/// exhausted.
+const DYNAMIC_PAYLOAD_TYPE_RANGES: [RangeInclusive<u8>; 2] = [96..=127, 35..=63];
+
+/// Hands out RTP payload type IDs, keeping track of which ones are already taken.
+///
+/// A preferred ID is honored when it is still free; on collision (or when no preference is
+/// given) a fresh dynamic ID is allocated instead of failing outright. RTX, RED and FEC
+/// companion codecs go through the same allocator as regular media codecs, so there is a single
+/// place that can run out of IDs.
+struct PayloadTypeAllocator {
+    used: BTreeSet<u8>,
+}
+
+impl PayloadTypeAllocator {
+    fn new() -> Self {
+        Self {
+            used: BTreeSet::new(),
+        }
+    }
+
+    /// Reserve `preferred` if given and still free, otherwise allocate a fresh dynamic ID.
+    /// Returns `None` once both dynamic ranges are exhausted.
+    fn request_id(&mut self, preferred: Option<u8>) -> Option<u8> {
+        if let Some(preferred) = preferred {
+            if self.used.insert(preferred) {
+                return Some(preferred);
+            }
+        }
+
+        let id = DYNAMIC_PAYLOAD_TYPE_RANGES
+            .iter()
+            .flat_map(|range| range.clone())
+            .find(|id| !self.used.contains(id))?;
+        self.used.insert(id);
+        Some(id)
+    }
+}
 
 #[doc(hidden)]
 #[derive(Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Deserialize, Serialize)]
@@ -174,10 +214,10 @@ pub(crate) fn generate_router_rtp_capabilities(
 
     validate_rtp_capabilities(&supported_rtp_capabilities)?;
 
-    let mut dynamic_payload_types = Vec::from(DYNAMIC_PAYLOAD_TYPES);
+    let mut payload_types = PayloadTypeAllocator::new();
     let mut caps = RtpCapabilitiesFinalized {
         codecs: vec![],
-        header_extensions: supported_rtp_capabilities.header_extensions,
+        header_extensions: with_playout_delay(supported_rtp_capabilities.header_extensions),
         fec_mechanisms: vec![],
     };
 
@@ -198,43 +238,22 @@ pub(crate) fn generate_router_rtp_capabilities(
             }
         };
 
-        let preferred_payload_type = match media_codec.preferred_payload_type() {
-            // If the given media codec has preferred_payload_type, keep it.
-            Some(preferred_payload_type) => {
-                // Also remove the payload_type from the list of available dynamic values.
-                dynamic_payload_types.retain(|&pt| pt != preferred_payload_type);
-
-                preferred_payload_type
-            }
-            None => {
-                match codec.preferred_payload_type() {
-                    // Otherwise if the supported codec has preferredPayloadType, use it.
-                    Some(preferred_payload_type) => {
-                        // No need to remove it from the list since it's not a dynamic value.
-                        preferred_payload_type
-                    }
-                    // Otherwise choose a dynamic one.
-                    None => {
-                        if dynamic_payload_types.is_empty() {
-                            return Err(RtpCapabilitiesError::CannotAllocate);
-                        }
-                        // Take the first available payload type and remove it from the list.
-                        dynamic_payload_types.remove(0)
-                    }
-                }
-            }
+        let preferred_payload_type = match media_codec
+            .preferred_payload_type()
+            .or_else(|| codec.preferred_payload_type())
+        {
+            // Keep the requested (or supported codec's) preferred ID if free; on collision
+            // silently fall back to a fresh dynamic ID instead of failing.
+            Some(preferred_payload_type) => payload_types
+                .request_id(Some(preferred_payload_type))
+                .ok_or(RtpCapabilitiesError::CannotAllocate)?,
+            // Otherwise choose a dynamic one.
+            None => payload_types
+                .request_id(None)
+                .ok_or(RtpCapabilitiesError::CannotAllocate)?,
         };
 
-        // Ensure there is not duplicated preferredPayloadType values.
-        for codec in caps.codecs.iter() {
-            if codec.preferred_payload_type() == preferred_payload_type {
-                return Err(RtpCapabilitiesError::DuplicatedPreferredPayloadType(
-                    preferred_payload_type,
-                ));
-            }
-        }
-
-        let codec_finalized = match codec {
+        let mut codec_finalized = match codec {
             RtpCodecCapability::Audio {
                 mime_type,
                 preferred_payload_type: _,
@@ -275,13 +294,46 @@ pub(crate) fn generate_router_rtp_capabilities(
             },
         };
 
-        // Add a RTX video codec if video.
-        if matches!(codec_finalized, RtpCodecCapabilityFinalized::Video {..}) {
-            if dynamic_payload_types.is_empty() {
-                return Err(RtpCapabilitiesError::CannotAllocate);
+        // RED and FlexFEC/ULPFEC are resiliency codecs: they never get a RTX companion of their
+        // own, and they make the corresponding mechanism available to consumers.
+        if let Some(fec_mechanism) = fec_mechanism_of(codec_finalized.mime_type()) {
+            if !caps.fec_mechanisms.contains(&fec_mechanism.to_string()) {
+                caps.fec_mechanisms.push(fec_mechanism.to_string());
             }
-            // Take the first available payload_type and remove it from the list.
-            let payload_type = dynamic_payload_types.remove(0);
+
+            // RED carries the payload types of the codec(s) it protects in its fmtp, e.g.
+            // `"111/111"` for a single primary codec.
+            if is_red(codec_finalized.mime_type()) {
+                // RED is audio-only, so its primary codec must be too: `caps.codecs` mixes audio
+                // and video codecs in whatever order `media_codecs` was passed, and without this
+                // filter a router configured e.g. `[Opus, VP8, H264, RED]` would bind RED's fmtp
+                // to H264's payload type instead of Opus's.
+                if let Some(primary_codec) = caps.codecs.iter().rev().find(|cap_codec| {
+                    matches!(cap_codec.mime_type(), MimeType::Audio(_))
+                        && !cap_codec.is_rtx()
+                        && fec_mechanism_of(cap_codec.mime_type()).is_none()
+                }) {
+                    // RED's fmtp has no `key=value` shape, just the ordered PT list, so it is
+                    // stored as a valueless parameter whose key *is* that list.
+                    let primary_pt = primary_codec.preferred_payload_type();
+                    codec_finalized
+                        .parameters_mut()
+                        .insert(format!("{primary_pt}/{primary_pt}").as_str(), 1u32.into());
+                }
+            }
+
+            caps.codecs.push(codec_finalized);
+            continue;
+        }
+
+        // Add a RTX video codec if video, unless it is an uncompressed/raw codec: raw video is
+        // never retransmitted, it is meant for low-latency studio/broadcast use cases.
+        if matches!(codec_finalized, RtpCodecCapabilityFinalized::Video {..})
+            && !matches!(codec_finalized.mime_type(), MimeType::Video(MimeTypeVideo::Raw))
+        {
+            let payload_type = payload_types
+                .request_id(None)
+                .ok_or(RtpCapabilitiesError::CannotAllocate)?;
 
             let rtx_codec = RtpCodecCapabilityFinalized::Video {
                 mime_type: MimeTypeVideo::RTX,
@@ -306,6 +358,154 @@ pub(crate) fn generate_router_rtp_capabilities(
     Ok(caps)
 }
 
+/// Returns the FEC mechanism name (as it should appear in `fec_mechanisms`) for codecs that
+/// implement forward error correction, `None` for regular media/RTX codecs.
+fn fec_mechanism_of(mime_type: MimeType) -> Option<&'static str> {
+    match mime_type {
+        MimeType::Audio(MimeTypeAudio::RED) => Some("RED"),
+        MimeType::Video(MimeTypeVideo::ULPFEC) => Some("ULPFEC"),
+        MimeType::Video(MimeTypeVideo::FLEXFEC) => Some("FlexFEC"),
+        _ => None,
+    }
+}
+
+/// Whether `mime_type` is the audio redundancy (RED) codec.
+pub(crate) fn is_red(mime_type: MimeType) -> bool {
+    matches!(mime_type, MimeType::Audio(MimeTypeAudio::RED))
+}
+
+/// Whether `mime_type` carries supplementary information (RFC 4733 telephone-event, RFC 3389
+/// comfort noise) rather than encoded media, so it needs neither a RTX companion nor BWE/NACK
+/// feedback.
+fn is_supplementary(mime_type: MimeType) -> bool {
+    matches!(
+        mime_type,
+        MimeType::Audio(MimeTypeAudio::TelephoneEvent) | MimeType::Audio(MimeTypeAudio::CN)
+    )
+}
+
+/// Parse a RFC 4733 telephone-event range fmtp token (e.g. `"0-15"`) into `(low, high)`. Like
+/// RED's fmtp PT list, the range has no `key=value` shape, so it is stored as a bare parameter
+/// key (see the RED fmtp handling in `generate_router_rtp_capabilities` above).
+fn telephone_event_range(parameters: &RtpCodecParametersParameters) -> Option<(u8, u8)> {
+    parameters.iter().find_map(|(key, _)| {
+        let (low, high) = key.as_str().split_once('-')?;
+        Some((low.trim().parse().ok()?, high.trim().parse().ok()?))
+    })
+}
+
+/// Minimum and maximum target playout delay for a Consumer's `PlayoutDelay` RTP header
+/// extension, in 10 ms units (0..=4095, i.e. 0..=40950 ms), per
+/// `http://www.webrtc.org/experiments/rtp-hdrext/playout-delay`. This is the same
+/// `targetDelay` concept cast-streaming senders use to trade latency against smoothness.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) struct PlayoutDelay {
+    pub(crate) min: u16,
+    pub(crate) max: u16,
+}
+
+/// Whether a Consumer may honor `playout_delay`: the extension only reaches the wire if it
+/// survived `get_consumer_rtp_parameters`'s header extension intersection, i.e. both the
+/// consumable parameters and the remote RTP capabilities advertised it.
+pub(crate) fn playout_delay_is_negotiated(
+    header_extensions: &[RtpHeaderExtensionParameters],
+) -> bool {
+    header_extensions
+        .iter()
+        .any(|ext| ext.uri == RtpHeaderExtensionUri::PlayoutDelay)
+}
+
+/// Ensure the router advertises `PlayoutDelay` for both audio and video, for routers whose
+/// `supported_rtp_capabilities` table predates this extension.
+fn with_playout_delay(
+    mut header_extensions: Vec<RtpHeaderExtension>,
+) -> Vec<RtpHeaderExtension> {
+    for kind in [MediaKind::Audio, MediaKind::Video] {
+        let already_advertised = header_extensions
+            .iter()
+            .any(|ext| ext.kind == Some(kind) && ext.uri == RtpHeaderExtensionUri::PlayoutDelay);
+
+        if !already_advertised {
+            header_extensions.push(RtpHeaderExtension {
+                kind: Some(kind),
+                uri: RtpHeaderExtensionUri::PlayoutDelay,
+                preferred_id: 13,
+                preferred_encrypt: false,
+                direction: RtpHeaderExtensionDirection::SendRecv,
+            });
+        }
+    }
+
+    header_extensions
+}
+
+/// Canonical `(media kind, mime type, clock rate, channels)` for the classic RFC 3551 static
+/// payload types (PCMU, GSM, G723, PCMA, G722, CN, ...), mirroring the `STATIC_PAYLOAD_INFO` table
+/// other WebRTC/RTSP SDP stacks use to interop with telephony peers that never negotiate a dynamic
+/// payload type.
+pub(crate) fn static_payload_type_defaults(
+    payload_type: u8,
+) -> Option<(MediaKind, MimeType, NonZeroU32, Option<NonZeroU8>)> {
+    let mono = Some(NonZeroU8::new(1).unwrap());
+    let clock_rate_8k = NonZeroU32::new(8000).unwrap();
+
+    Some(match payload_type {
+        0 => (MediaKind::Audio, MimeType::Audio(MimeTypeAudio::Pcmu), clock_rate_8k, mono),
+        3 => (MediaKind::Audio, MimeType::Audio(MimeTypeAudio::Gsm), clock_rate_8k, mono),
+        4 => (MediaKind::Audio, MimeType::Audio(MimeTypeAudio::G723), clock_rate_8k, mono),
+        8 => (MediaKind::Audio, MimeType::Audio(MimeTypeAudio::Pcma), clock_rate_8k, mono),
+        9 => (MediaKind::Audio, MimeType::Audio(MimeTypeAudio::G722), clock_rate_8k, mono),
+        13 => (MediaKind::Audio, MimeType::Audio(MimeTypeAudio::CN), clock_rate_8k, mono),
+        _ => return None,
+    })
+}
+
+/// Build the media codec capability for a classic RFC 3551 static payload type (see
+/// `static_payload_type_defaults`), so a `RouterOptions::media_codecs` entry for e.g. PCMU can be
+/// written as `rtp_codec_capability_for_static_payload_type(0)` instead of the caller having to
+/// know its canonical clock rate and channel count.
+pub(crate) fn rtp_codec_capability_for_static_payload_type(
+    payload_type: u8,
+) -> Option<RtpCodecCapability> {
+    let (kind, mime_type, clock_rate, channels) = static_payload_type_defaults(payload_type)?;
+
+    Some(match (kind, mime_type) {
+        (MediaKind::Audio, MimeType::Audio(mime_type)) => RtpCodecCapability::Audio {
+            mime_type,
+            preferred_payload_type: Some(payload_type),
+            clock_rate,
+            channels: channels.unwrap_or_else(|| NonZeroU8::new(1).unwrap()),
+            parameters: RtpCodecParametersParameters::new(),
+            rtcp_feedback: vec![],
+        },
+        (MediaKind::Video, MimeType::Video(mime_type)) => RtpCodecCapability::Video {
+            mime_type,
+            preferred_payload_type: Some(payload_type),
+            clock_rate,
+            parameters: RtpCodecParametersParameters::new(),
+            rtcp_feedback: vec![],
+        },
+        _ => unreachable!("static_payload_type_defaults pairs kind with a matching mime type"),
+    })
+}
+
+/// Apply the parameter negotiated by `match_codecs` (H.264's `profile-level-id`, AV1's
+/// `level-idx`; `None` for every other codec) to a clone of `cap_codec`, borrowing it unchanged
+/// when there is nothing to negotiate.
+fn with_negotiated_parameter(
+    cap_codec: &RtpCodecCapabilityFinalized,
+    negotiated: Option<(&'static str, String)>,
+) -> Cow<RtpCodecCapabilityFinalized> {
+    match negotiated {
+        Some((name, value)) => {
+            let mut cap_codec = cap_codec.clone();
+            cap_codec.parameters_mut().insert(name, value);
+            Cow::Owned(cap_codec)
+        }
+        None => Cow::Borrowed(cap_codec),
+    }
+}
+
 /// Get a mapping of codec payloads and encodings of the given Producer RTP parameters as values
 /// expected by the Router.
 pub(crate) fn get_producer_rtp_parameters_mapping(
@@ -323,23 +523,12 @@ pub(crate) fn get_producer_rtp_parameters_mapping(
             continue;
         }
 
-        // Search for the same media codec in capabilities.
+        // Search for the same media codec in capabilities, carrying over the negotiated
+        // `profile-level-id` (for H.264) into the mapped capability codec.
         match rtp_capabilities.codecs.iter().find_map(|cap_codec| {
             match_codecs(codec.into(), cap_codec.into(), true)
                 .ok()
-                .map(|profile_level_id| {
-                    // This is rather ugly, but we need to fix `profile-level-id` and this was the
-                    // quickest way to do it
-                    if let Some(profile_level_id) = profile_level_id {
-                        let mut cap_codec = cap_codec.clone();
-                        cap_codec
-                            .parameters_mut()
-                            .insert("profile-level-id", profile_level_id);
-                        Cow::Owned(cap_codec)
-                    } else {
-                        Cow::Borrowed(cap_codec)
-                    }
-                })
+                .map(|negotiated| with_negotiated_parameter(cap_codec, negotiated))
         }) {
             Some(matched_codec_capability) => {
                 codec_to_cap_codec.insert(codec, matched_codec_capability);
@@ -422,14 +611,31 @@ pub(crate) fn get_producer_rtp_parameters_mapping(
     let mut mapped_ssrc: u32 = generate_ssrc();
 
     for encoding in rtp_parameters.encodings.iter() {
-        rtp_mapping.encodings.push(RtpMappingEncoding {
-            ssrc: encoding.ssrc,
-            rid: encoding.rid.clone(),
-            scalability_mode: encoding.scalability_mode.clone(),
-            mapped_ssrc,
-        });
+        // A lone SVC encoding (VP9/AV1 `LxTy` scalability modes) carries several spatial layers
+        // over a single SSRC, so it needs one mapped SSRC per spatial layer, the same as a
+        // simulcast Producer needs one per encoding, for consumers to be able to select/switch
+        // between them.
+        let spatial_layers = if rtp_parameters.encodings.len() == 1 {
+            encoding
+                .scalability_mode
+                .as_deref()
+                .and_then(|mode| mode.parse::<ScalabilityMode>().ok())
+                .map(|mode| mode.spatial_layers)
+                .unwrap_or(1)
+        } else {
+            1
+        };
+
+        for _ in 0..spatial_layers {
+            rtp_mapping.encodings.push(RtpMappingEncoding {
+                ssrc: encoding.ssrc,
+                rid: encoding.rid.clone(),
+                scalability_mode: encoding.scalability_mode.clone(),
+                mapped_ssrc,
+            });
 
-        mapped_ssrc += 1;
+            mapped_ssrc += 1;
+        }
     }
 
     Ok(rtp_mapping)
@@ -478,7 +684,12 @@ pub(crate) fn get_consumable_rtp_parameters(
                     channels: *channels,
                     // Keep the Producer codec parameters.
                     parameters: codec.parameters().clone(),
-                    rtcp_feedback: rtcp_feedback.clone(),
+                    // Telephone-event/CN carry no media to retransmit or congestion-control.
+                    rtcp_feedback: if is_supplementary(*mime_type) {
+                        vec![]
+                    } else {
+                        rtcp_feedback.clone()
+                    },
                 }
             }
             RtpCodecCapabilityFinalized::Video {
@@ -581,22 +792,31 @@ pub(crate) fn get_consumable_rtp_parameters(
         consumable_params.header_extensions.push(consumable_ext);
     }
 
-    for (consumable_encoding, mapped_ssrc) in params.encodings.iter().zip(
-        rtp_mapping
-            .encodings
-            .iter()
-            .map(|encoding| encoding.mapped_ssrc),
-    ) {
-        let mut consumable_encoding = consumable_encoding.clone();
-        // Remove useless fields.
-        consumable_encoding.rid.take();
-        consumable_encoding.rtx.take();
-        consumable_encoding.codec_payload_type.take();
+    // `rtp_mapping.encodings` has one entry per mapped SSRC, which for a SVC encoding (see
+    // `get_producer_rtp_parameters_mapping`) is several entries per source encoding rather than
+    // one; group consecutive entries that came from the same source encoding (recognizable by
+    // their shared, copied-over ssrc/rid) back together.
+    let mut mapped_encodings = rtp_mapping.encodings.iter().peekable();
+
+    for source_encoding in params.encodings.iter() {
+        while let Some(&mapped_encoding) = mapped_encodings.peek() {
+            if mapped_encoding.ssrc != source_encoding.ssrc || mapped_encoding.rid != source_encoding.rid
+            {
+                break;
+            }
+            mapped_encodings.next();
 
-        // Set the mapped ssrc.
-        consumable_encoding.ssrc = Some(mapped_ssrc);
+            let mut consumable_encoding = source_encoding.clone();
+            // Remove useless fields.
+            consumable_encoding.rid.take();
+            consumable_encoding.rtx.take();
+            consumable_encoding.codec_payload_type.take();
 
-        consumable_params.encodings.push(consumable_encoding);
+            // Set the mapped ssrc.
+            consumable_encoding.ssrc = Some(mapped_encoding.mapped_ssrc);
+
+            consumable_params.encodings.push(consumable_encoding);
+        }
     }
 
     consumable_params.rtcp = RtcpParameters {
@@ -618,6 +838,18 @@ pub(crate) fn can_consume(
     let mut matching_codecs = Vec::<&RtpCodecParameters>::new();
 
     for codec in consumable_params.codecs.iter() {
+        if let Some(fec_mechanism) = fec_mechanism_of(codec.mime_type()) {
+            // RED/FlexFEC/ULPFEC are only consumable when the capabilities also advertise the
+            // matching resiliency mechanism, not just a codec with the same mime type.
+            if !caps
+                .fec_mechanisms
+                .iter()
+                .any(|mechanism| mechanism == fec_mechanism)
+            {
+                continue;
+            }
+        }
+
         if caps
             .codecs
             .iter()
@@ -627,21 +859,96 @@ pub(crate) fn can_consume(
         }
     }
 
-    // Ensure there is at least one media codec.
+    // Ensure there is at least one proper media codec (not RTX, RED or FEC). RED/FEC codecs can
+    // legitimately be listed before their primary codec (e.g. a Chrome offer listing RED's
+    // payload type before Opus's), so every entry must be checked, not just the first.
     Ok(matching_codecs
-        .get(0)
-        .map(|codec| !codec.is_rtx())
-        .unwrap_or_default())
+        .iter()
+        .any(|codec| !codec.is_rtx() && fec_mechanism_of(codec.mime_type()).is_none()))
+}
+
+/// Steers which negotiated media codec (and its paired RTX codec) `get_consumer_rtp_parameters`
+/// places first in the Consumer's codec list, e.g. to strongly prefer H264's `42e01f` profile
+/// over `640032`, or VP9 over VP8, regardless of the order the consumable parameters list them
+/// in.
+#[derive(Debug, Clone)]
+pub(crate) struct ConsumerCodecPreference {
+    pub(crate) mime_type: MimeType,
+    /// When set, only a codec whose parameters contain this `(key, value)` pair is preferred
+    /// (e.g. H264's `profile-level-id`). `None` prefers any codec matching `mime_type`.
+    pub(crate) parameter: Option<(&'static str, RtpCodecParametersParametersValue)>,
+}
+
+/// Move `preference`'s matching media codec (and its paired RTX codec, if any) to the front of
+/// `codecs`, preserving the relative order of everything else and the RTX-association invariant
+/// that every RTX codec directly follows the media codec it protects in the original list.
+fn reorder_preferred_codec(codecs: &mut Vec<RtpCodecParameters>, preference: &ConsumerCodecPreference) {
+    let preferred_idx = match codecs.iter().position(|codec| {
+        !codec.is_rtx()
+            && codec.mime_type() == preference.mime_type
+            && preference
+                .parameter
+                .as_ref()
+                .map(|(key, value)| codec.parameters().get(key) == Some(value))
+                .unwrap_or(true)
+    }) {
+        Some(idx) => idx,
+        None => return,
+    };
+
+    let preferred_pt = codecs[preferred_idx].payload_type();
+    let rtx_idx = codecs.iter().position(|codec| {
+        matches!(
+            codec.parameters().get("apt"),
+            Some(RtpCodecParametersParametersValue::Number(apt)) if *apt as u8 == preferred_pt
+        )
+    });
+
+    let mut preferred = vec![codecs.remove(preferred_idx)];
+    if let Some(rtx_idx) = rtx_idx {
+        // `rtx_idx` was computed before the media codec's removal shifted later indices down by one.
+        let rtx_idx = if rtx_idx > preferred_idx {
+            rtx_idx - 1
+        } else {
+            rtx_idx
+        };
+        preferred.push(codecs.remove(rtx_idx));
+    }
+
+    preferred.extend(codecs.drain(..));
+    *codecs = preferred;
+}
+
+/// Parse a cast-style (openscreen) `maxFrameRate` value into whole frames per second: plain
+/// integers (`"30"`) and rationals that reduce to one (`"60000/1000"`) are both accepted.
+pub(crate) fn parse_max_framerate(value: &str) -> Option<u32> {
+    match value.split_once('/') {
+        Some((numerator, denominator)) => {
+            let numerator: u32 = numerator.trim().parse().ok()?;
+            let denominator: u32 = denominator.trim().parse().ok()?;
+
+            if denominator == 0 {
+                return None;
+            }
+
+            Some(numerator / denominator)
+        }
+        None => value.trim().parse().ok(),
+    }
 }
 
 /// Generate RTP parameters for a specific Consumer.
 ///
 /// It reduces encodings to just one and takes into account given RTP capabilities to reduce codecs,
-/// codecs' RTCP feedback and header extensions, and also enables or disabled RTX.
+/// codecs' RTCP feedback and header extensions, and also enables or disabled RTX. When
+/// `preferred_codec` is given, its media codec (and paired RTX) is placed first so downstream SFU
+/// logic and the browser pick it.
 pub(crate) fn get_consumer_rtp_parameters(
     consumable_params: &RtpParameters,
     caps: RtpCapabilities,
-) -> Result<RtpParameters, ConsumerRtpParametersError> {
+    preferred_codec: Option<ConsumerCodecPreference>,
+    preferred_playout_delay: Option<PlayoutDelay>,
+) -> Result<(RtpParameters, Option<PlayoutDelay>), ConsumerRtpParametersError> {
     let mut consumer_params = RtpParameters::default();
     consumer_params.rtcp = consumable_params.rtcp.clone();
 
@@ -658,7 +965,12 @@ pub(crate) fn get_consumer_rtp_parameters(
             .iter()
             .find(|cap_codec| match_codecs(cap_codec.deref().into(), (&codec).into(), true).is_ok())
         {
-            *codec.rtcp_feedback_mut() = matched_cap_codec.rtcp_feedback().clone();
+            *codec.rtcp_feedback_mut() = if is_supplementary(codec.mime_type()) {
+                // Telephone-event/CN carry no media to retransmit or congestion-control.
+                vec![]
+            } else {
+                matched_cap_codec.rtcp_feedback().clone()
+            };
             consumer_params.codecs.push(codec);
         }
     }
@@ -687,6 +999,28 @@ pub(crate) fn get_consumer_rtp_parameters(
         consumer_params.codecs.remove(idx);
     }
 
+    // Must also drop RED/FlexFEC/ULPFEC codecs the remote capabilities don't advertise support
+    // for, the same way useless RTX codecs are removed above.
+    let mut remove_fec_codecs = Vec::new();
+    for (idx, codec) in consumer_params.codecs.iter().enumerate() {
+        if let Some(fec_mechanism) = fec_mechanism_of(codec.mime_type()) {
+            if !caps
+                .fec_mechanisms
+                .iter()
+                .any(|mechanism| mechanism == fec_mechanism)
+            {
+                remove_fec_codecs.push(idx);
+            }
+        }
+    }
+    for idx in remove_fec_codecs.into_iter().rev() {
+        consumer_params.codecs.remove(idx);
+    }
+
+    if let Some(preference) = &preferred_codec {
+        reorder_preferred_codec(&mut consumer_params.codecs, preference);
+    }
+
     // Ensure there is at least one media codec.
     if consumer_params.codecs.is_empty() || consumer_params.codecs[0].is_rtx() {
         return Err(ConsumerRtpParametersError::NoCompatibleMediaCodecs);
@@ -774,13 +1108,34 @@ pub(crate) fn get_consumer_rtp_parameters(
         .max()
         .flatten();
 
+    // Use the maximum max_framerate in any encoding and honor it in the Consumer's encoding.
+    consumer_encoding.max_framerate = consumable_params
+        .encodings
+        .iter()
+        .map(|encoding| encoding.max_framerate)
+        .max()
+        .flatten();
+
+    // If any of the consumable_params.encodings has resolutions, carry them over verbatim
+    // (assume all encodings have the same value).
+    consumer_encoding.resolutions = consumable_params
+        .encodings
+        .iter()
+        .find_map(|encoding| encoding.resolutions.clone());
+
     // Set a single encoding for the Consumer.
     consumer_params.encodings.push(consumer_encoding);
 
     // Copy verbatim.
     consumer_params.rtcp = consumable_params.rtcp.clone();
 
-    Ok(consumer_params)
+    // Only honor the caller's requested playout delay if it actually survived the header
+    // extension intersection above, i.e. both the consumable parameters and the remote RTP
+    // capabilities advertised `PlayoutDelay`.
+    let playout_delay = preferred_playout_delay
+        .filter(|_| playout_delay_is_negotiated(&consumer_params.header_extensions));
+
+    Ok((consumer_params, playout_delay))
 }
 
 /// Generate RTP parameters for a pipe Consumer.
@@ -849,7 +1204,7 @@ pub(crate) fn get_pipe_consumer_rtp_parameters(
     consumer_params
 }
 
-struct CodecToMatch<'a> {
+pub(crate) struct CodecToMatch<'a> {
     channels: Option<NonZeroU8>,
     clock_rate: NonZeroU32,
     mime_type: MimeType,
@@ -946,17 +1301,35 @@ impl<'a> From<&'a RtpCodecParameters> for CodecToMatch<'a> {
     }
 }
 
-/// Returns selected `Ok(Some(profile-level-id))` for H264 codec and `Ok(None)` for others
-fn match_codecs(
+/// Reads the AV1 `level-idx` fmtp parameter, defaulting to 5 (level 3.1) when absent, per the
+/// AV1 RTP payload format registration.
+fn av1_level_idx(parameters: &RtpCodecParametersParameters) -> u32 {
+    match parameters
+        .get("level-idx")
+        .unwrap_or(&RtpCodecParametersParametersValue::Number(5))
+    {
+        RtpCodecParametersParametersValue::Number(level_idx) => *level_idx,
+        RtpCodecParametersParametersValue::String(_) => 5,
+    }
+}
+
+/// Returns `Ok(Some((parameter_name, value)))` when strict matching negotiated a parameter that
+/// must be carried over into the answer (H264's `profile-level-id`, AV1's `level-idx`), `Ok(None)`
+/// for every other codec or in non-strict mode.
+pub(crate) fn match_codecs(
     codec_a: CodecToMatch,
     codec_b: CodecToMatch,
     strict: bool,
-) -> Result<Option<String>, ()> {
+) -> Result<Option<(&'static str, String)>, ()> {
     if codec_a.mime_type != codec_b.mime_type {
         return Err(());
     }
 
-    if codec_a.channels != codec_b.channels {
+    // RFC 4733 telephone-event and RFC 3389 comfort noise carry supplementary information rather
+    // than encoded media, so (unlike regular media codecs) they don't need matching channels.
+    let ignore_channels = is_supplementary(codec_a.mime_type);
+
+    if !ignore_channels && codec_a.channels != codec_b.channels {
         return Err(());
     }
 
@@ -965,6 +1338,26 @@ fn match_codecs(
     }
     // Per codec special checks.
     match codec_a.mime_type {
+        MimeType::Audio(MimeTypeAudio::TelephoneEvent) => {
+            // If strict matching, treat the `0-15`/`0-16` event-range fmtp as compatible when one
+            // is a subset of the other, per RFC 4733.
+            if strict {
+                if let (Some(range_a), Some(range_b)) = (
+                    telephone_event_range(codec_a.parameters),
+                    telephone_event_range(codec_b.parameters),
+                ) {
+                    let (low_a, high_a) = range_a;
+                    let (low_b, high_b) = range_b;
+                    let is_subset =
+                        (low_a >= low_b && high_a <= high_b) || (low_b >= low_a && high_b <= high_a);
+
+                    if !is_subset {
+                        return Err(());
+                    }
+                }
+            }
+        }
+
         MimeType::Video(MimeTypeVideo::H264) => {
             let packetization_mode_a = codec_a
                 .parameters
@@ -979,29 +1372,30 @@ fn match_codecs(
                 return Err(());
             }
 
-            // If strict matching check profile-level-id.
+            // If strict matching check profile-level-id. A missing value defaults to
+            // `42001f` (Constrained Baseline, level 3.1) per RFC 6184.
             if strict {
-                let profile_level_id_a =
-                    codec_a
-                        .parameters
-                        .get("profile-level-id")
-                        .and_then(|p| match p {
-                            RtpCodecParametersParametersValue::String(s) => Some(s.as_str()),
-                            RtpCodecParametersParametersValue::Number(_) => None,
-                        });
-                let profile_level_id_b =
-                    codec_b
-                        .parameters
-                        .get("profile-level-id")
-                        .and_then(|p| match p {
-                            RtpCodecParametersParametersValue::String(s) => Some(s.as_str()),
-                            RtpCodecParametersParametersValue::Number(_) => None,
-                        });
+                let profile_level_id_a = codec_a
+                    .parameters
+                    .get("profile-level-id")
+                    .and_then(|p| match p {
+                        RtpCodecParametersParametersValue::String(s) => Some(s.as_str()),
+                        RtpCodecParametersParametersValue::Number(_) => None,
+                    })
+                    .unwrap_or(H264_DEFAULT_PROFILE_LEVEL_ID);
+                let profile_level_id_b = codec_b
+                    .parameters
+                    .get("profile-level-id")
+                    .and_then(|p| match p {
+                        RtpCodecParametersParametersValue::String(s) => Some(s.as_str()),
+                        RtpCodecParametersParametersValue::Number(_) => None,
+                    })
+                    .unwrap_or(H264_DEFAULT_PROFILE_LEVEL_ID);
 
                 let (profile_level_id_a, profile_level_id_b) =
                     match h264_profile_level_id::is_same_profile(
-                        profile_level_id_a,
-                        profile_level_id_b,
+                        Some(profile_level_id_a),
+                        Some(profile_level_id_b),
                     ) {
                         Some((profile_level_id_a, profile_level_id_b)) => {
                             (profile_level_id_a, profile_level_id_b)
@@ -1029,13 +1423,25 @@ fn match_codecs(
 
                 return match selected_profile_level_id {
                     Ok(selected_profile_level_id) => {
-                        Ok(Some(selected_profile_level_id.to_string()))
+                        Ok(Some(("profile-level-id", selected_profile_level_id.to_string())))
                     }
                     Err(_) => Err(()),
                 };
             }
         }
 
+        MimeType::Audio(MimeTypeAudio::Mp4aLatm) | MimeType::Audio(MimeTypeAudio::Mpeg4Generic) => {
+            // Two AAC streams are only interchangeable if they share the same StreamMuxConfig
+            // (`config`/`cpresent`/`profile-level-id`) and packetization (`mode`, e.g. `AAC-hbr`).
+            if strict {
+                for key in ["config", "cpresent", "profile-level-id", "mode"] {
+                    if codec_a.parameters.get(key) != codec_b.parameters.get(key) {
+                        return Err(());
+                    }
+                }
+            }
+        }
+
         MimeType::Video(MimeTypeVideo::VP9) => {
             // If strict matching check profile-id.
             if strict {
@@ -1054,6 +1460,44 @@ fn match_codecs(
             }
         }
 
+        MimeType::Video(MimeTypeVideo::AV1) => {
+            // If strict matching check profile and tier (defaulting to 0 when absent) and clamp
+            // the answer's level-idx to the lower of the two, per the AV1 RTP payload format
+            // registration (profile/tier must match exactly, level-idx may be downgraded).
+            if strict {
+                let profile_a = codec_a
+                    .parameters
+                    .get("profile")
+                    .unwrap_or(&RtpCodecParametersParametersValue::Number(0));
+                let profile_b = codec_b
+                    .parameters
+                    .get("profile")
+                    .unwrap_or(&RtpCodecParametersParametersValue::Number(0));
+
+                if profile_a != profile_b {
+                    return Err(());
+                }
+
+                let tier_a = codec_a
+                    .parameters
+                    .get("tier")
+                    .unwrap_or(&RtpCodecParametersParametersValue::Number(0));
+                let tier_b = codec_b
+                    .parameters
+                    .get("tier")
+                    .unwrap_or(&RtpCodecParametersParametersValue::Number(0));
+
+                if tier_a != tier_b {
+                    return Err(());
+                }
+
+                let level_idx_a = av1_level_idx(codec_a.parameters);
+                let level_idx_b = av1_level_idx(codec_b.parameters);
+
+                return Ok(Some(("level-idx", level_idx_a.min(level_idx_b).to_string())));
+            }
+        }
+
         _ => {}
     }
 
@@ -1063,7 +1507,7 @@ fn match_codecs(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::rtp_parameters::{MimeTypeAudio, RtpHeaderExtension};
+    use crate::rtp_parameters::MimeTypeAudio;
     use std::iter;
 
     #[test]
@@ -1108,7 +1552,7 @@ mod tests {
             vec![
                 RtpCodecCapabilityFinalized::Audio {
                     mime_type: MimeTypeAudio::Opus,
-                    preferred_payload_type: 100, // 100 is the first available dynamic PT.
+                    preferred_payload_type: 96, // 96 is the first available dynamic PT.
                     clock_rate: NonZeroU32::new(48000).unwrap(),
                     channels: NonZeroU8::new(2).unwrap(),
                     parameters: RtpCodecParametersParameters::from([
@@ -1132,14 +1576,14 @@ mod tests {
                 },
                 RtpCodecCapabilityFinalized::Video {
                     mime_type: MimeTypeVideo::RTX,
-                    preferred_payload_type: 101, // 101 is the second available dynamic PT.
+                    preferred_payload_type: 97, // 97 is the second available dynamic PT.
                     clock_rate: NonZeroU32::new(90000).unwrap(),
                     parameters: RtpCodecParametersParameters::from([("apt", 125u32.into())]),
                     rtcp_feedback: vec![],
                 },
                 RtpCodecCapabilityFinalized::Video {
                     mime_type: MimeTypeVideo::H264,
-                    preferred_payload_type: 102, // 102 is the third available dynamic PT.
+                    preferred_payload_type: 98, // 98 is the third available dynamic PT.
                     clock_rate: NonZeroU32::new(90000).unwrap(),
                     parameters: RtpCodecParametersParameters::from([
                         ("packetization-mode", 0u32.into()),
@@ -1157,13 +1601,141 @@ mod tests {
                 },
                 RtpCodecCapabilityFinalized::Video {
                     mime_type: MimeTypeVideo::RTX,
-                    preferred_payload_type: 103,
+                    preferred_payload_type: 99,
                     clock_rate: NonZeroU32::new(90000).unwrap(),
-                    parameters: RtpCodecParametersParameters::from([("apt", 102u32.into())]),
+                    parameters: RtpCodecParametersParameters::from([("apt", 98u32.into())]),
                     rtcp_feedback: vec![],
                 },
             ]
         );
+
+        // Both audio and video must advertise `PlayoutDelay`, regardless of what the
+        // `supported_rtp_capabilities` table backing this router happens to list.
+        for kind in [MediaKind::Audio, MediaKind::Video] {
+            assert!(rtp_capabilities.header_extensions.iter().any(|ext| {
+                ext.kind == Some(kind) && ext.uri == RtpHeaderExtensionUri::PlayoutDelay
+            }));
+        }
+    }
+
+    #[test]
+    fn generate_router_rtp_capabilities_red_binds_to_same_kind_primary_codec() {
+        // RED is audio-only; listing it after unrelated video codecs must not make it bind to
+        // the video codec that happens to be last in `caps.codecs`.
+        let media_codecs = vec![
+            RtpCodecCapability::Audio {
+                mime_type: MimeTypeAudio::Opus,
+                preferred_payload_type: None,
+                clock_rate: NonZeroU32::new(48000).unwrap(),
+                channels: NonZeroU8::new(2).unwrap(),
+                parameters: RtpCodecParametersParameters::new(),
+                rtcp_feedback: vec![],
+            },
+            RtpCodecCapability::Video {
+                mime_type: MimeTypeVideo::VP8,
+                preferred_payload_type: None,
+                clock_rate: NonZeroU32::new(90000).unwrap(),
+                parameters: RtpCodecParametersParameters::new(),
+                rtcp_feedback: vec![],
+            },
+            RtpCodecCapability::Audio {
+                mime_type: MimeTypeAudio::RED,
+                preferred_payload_type: None,
+                clock_rate: NonZeroU32::new(48000).unwrap(),
+                channels: NonZeroU8::new(2).unwrap(),
+                parameters: RtpCodecParametersParameters::new(),
+                rtcp_feedback: vec![],
+            },
+        ];
+
+        let rtp_capabilities = generate_router_rtp_capabilities(media_codecs)
+            .expect("Failed to generate router RTP capabilities");
+
+        let opus_payload_type = rtp_capabilities
+            .codecs
+            .iter()
+            .find(|codec| codec.mime_type() == MimeType::Audio(MimeTypeAudio::Opus))
+            .unwrap()
+            .preferred_payload_type();
+
+        let red = rtp_capabilities
+            .codecs
+            .iter()
+            .find(|codec| codec.mime_type() == MimeType::Audio(MimeTypeAudio::RED))
+            .unwrap();
+
+        assert_eq!(
+            red.parameters().get(&format!("{opus_payload_type}/{opus_payload_type}")),
+            Some(&RtpCodecParametersParametersValue::Number(1)),
+        );
+    }
+
+    #[test]
+    fn can_consume_and_get_consumer_rtp_parameters_when_red_precedes_primary_codec() {
+        // RED's consumable entry can legitimately come before its primary codec's (e.g. a Chrome
+        // offer listing RED's payload type before Opus's), and both `can_consume` and
+        // `get_consumer_rtp_parameters` must not special-case codecs[0].
+        let opus = RtpCodecParameters::Audio {
+            mime_type: MimeTypeAudio::Opus,
+            payload_type: 111,
+            clock_rate: NonZeroU32::new(48000).unwrap(),
+            channels: NonZeroU8::new(2).unwrap(),
+            parameters: RtpCodecParametersParameters::new(),
+            rtcp_feedback: vec![],
+        };
+        let red = RtpCodecParameters::Audio {
+            mime_type: MimeTypeAudio::RED,
+            payload_type: 63,
+            clock_rate: NonZeroU32::new(48000).unwrap(),
+            channels: NonZeroU8::new(2).unwrap(),
+            parameters: RtpCodecParametersParameters::from([("111/111", 1u32.into())]),
+            rtcp_feedback: vec![],
+        };
+
+        let consumable_params = RtpParameters {
+            mid: None,
+            // RED listed first, Opus (the primary codec) second.
+            codecs: vec![red.clone(), opus.clone()],
+            header_extensions: vec![],
+            encodings: vec![RtpEncodingParameters {
+                ssrc: Some(11111111),
+                ..RtpEncodingParameters::default()
+            }],
+            rtcp: RtcpParameters::default(),
+        };
+
+        let caps = RtpCapabilities {
+            codecs: vec![
+                RtpCodecCapabilityFinalized::Audio {
+                    mime_type: MimeTypeAudio::RED,
+                    preferred_payload_type: 63,
+                    clock_rate: NonZeroU32::new(48000).unwrap(),
+                    channels: NonZeroU8::new(2).unwrap(),
+                    parameters: RtpCodecParametersParameters::from([("111/111", 1u32.into())]),
+                    rtcp_feedback: vec![],
+                },
+                RtpCodecCapabilityFinalized::Audio {
+                    mime_type: MimeTypeAudio::Opus,
+                    preferred_payload_type: 111,
+                    clock_rate: NonZeroU32::new(48000).unwrap(),
+                    channels: NonZeroU8::new(2).unwrap(),
+                    parameters: RtpCodecParametersParameters::new(),
+                    rtcp_feedback: vec![],
+                },
+            ],
+            header_extensions: vec![],
+            fec_mechanisms: vec!["RED".to_string()],
+        };
+
+        assert!(can_consume(&consumable_params, &caps).expect("Failed to check can_consume"));
+
+        let (consumer_params, _) = get_consumer_rtp_parameters(&consumable_params, caps, None, None)
+            .expect("Failed to get consumer RTP parameters");
+
+        assert!(consumer_params
+            .codecs
+            .iter()
+            .any(|codec| codec.mime_type() == MimeType::Audio(MimeTypeAudio::Opus)));
     }
 
     #[test]
@@ -1321,11 +1893,11 @@ mod tests {
             vec![
                 RtpMappingCodec {
                     payload_type: 111,
-                    mapped_payload_type: 101
+                    mapped_payload_type: 97
                 },
                 RtpMappingCodec {
                     payload_type: 112,
-                    mapped_payload_type: 102
+                    mapped_payload_type: 98
                 },
             ]
         );
@@ -1352,7 +1924,7 @@ mod tests {
             vec![
                 RtpCodecParameters::Video {
                     mime_type: MimeTypeVideo::H264,
-                    payload_type: 101,
+                    payload_type: 97,
                     clock_rate: NonZeroU32::new(90000).unwrap(),
                     parameters: RtpCodecParametersParameters::from([
                         ("foo", 1234u32.into()),
@@ -1369,9 +1941,9 @@ mod tests {
                 },
                 RtpCodecParameters::Video {
                     mime_type: MimeTypeVideo::RTX,
-                    payload_type: 102,
+                    payload_type: 98,
                     clock_rate: NonZeroU32::new(90000).unwrap(),
-                    parameters: RtpCodecParametersParameters::from([("apt", 101u32.into())]),
+                    parameters: RtpCodecParametersParameters::from([("apt", 97u32.into())]),
                     rtcp_feedback: vec![],
                 },
             ]
@@ -1527,16 +2099,21 @@ mod tests {
             fec_mechanisms: vec![],
         };
 
-        let consumer_rtp_parameters =
-            get_consumer_rtp_parameters(&consumable_rtp_parameters, remote_rtp_capabilities)
-                .expect("Failed to get consumer RTP parameters");
+        let (consumer_rtp_parameters, playout_delay) = get_consumer_rtp_parameters(
+            &consumable_rtp_parameters,
+            remote_rtp_capabilities,
+            None,
+            None,
+        )
+        .expect("Failed to get consumer RTP parameters");
+        assert_eq!(playout_delay, None);
 
         assert_eq!(
             consumer_rtp_parameters.codecs,
             vec![
                 RtpCodecParameters::Video {
                     mime_type: MimeTypeVideo::H264,
-                    payload_type: 101,
+                    payload_type: 97,
                     clock_rate: NonZeroU32::new(90000).unwrap(),
                     parameters: RtpCodecParametersParameters::from([
                         ("foo", 1234u32.into()),
@@ -1551,9 +2128,9 @@ mod tests {
                 },
                 RtpCodecParameters::Video {
                     mime_type: MimeTypeVideo::RTX,
-                    payload_type: 102,
+                    payload_type: 98,
                     clock_rate: NonZeroU32::new(90000).unwrap(),
-                    parameters: RtpCodecParametersParameters::from([("apt", 101u32.into())]),
+                    parameters: RtpCodecParametersParameters::from([("apt", 97u32.into())]),
                     rtcp_feedback: vec![],
                 },
             ]
@@ -1626,7 +2203,7 @@ mod tests {
             pipe_consumer_rtp_parameters.codecs,
             vec![RtpCodecParameters::Video {
                 mime_type: MimeTypeVideo::H264,
-                payload_type: 101,
+                payload_type: 97,
                 clock_rate: NonZeroU32::new(90000).unwrap(),
                 parameters: RtpCodecParametersParameters::from([
                     ("foo", 1234u32.into()),
@@ -1727,6 +2304,185 @@ mod tests {
         );
     }
 
+    fn consumable_params_and_caps_with_playout_delay(
+        advertise_playout_delay: bool,
+    ) -> (RtpParameters, RtpCapabilities) {
+        let codec = RtpCodecParameters::Video {
+            mime_type: MimeTypeVideo::VP8,
+            payload_type: 101,
+            clock_rate: NonZeroU32::new(90000).unwrap(),
+            parameters: RtpCodecParametersParameters::default(),
+            rtcp_feedback: vec![],
+        };
+
+        let mut header_extensions = vec![RtpHeaderExtensionParameters {
+            uri: RtpHeaderExtensionUri::MID,
+            id: 1,
+            encrypt: false,
+        }];
+        if advertise_playout_delay {
+            header_extensions.push(RtpHeaderExtensionParameters {
+                uri: RtpHeaderExtensionUri::PlayoutDelay,
+                id: 13,
+                encrypt: false,
+            });
+        }
+
+        let consumable_params = RtpParameters {
+            mid: None,
+            codecs: vec![codec.clone()],
+            header_extensions,
+            encodings: vec![RtpEncodingParameters {
+                ssrc: Some(11111111),
+                ..RtpEncodingParameters::default()
+            }],
+            rtcp: RtcpParameters::default(),
+        };
+
+        let mut cap_header_extensions = vec![RtpHeaderExtension {
+            kind: Some(MediaKind::Video),
+            uri: RtpHeaderExtensionUri::MID,
+            preferred_id: 1,
+            preferred_encrypt: false,
+            direction: RtpHeaderExtensionDirection::SendRecv,
+        }];
+        if advertise_playout_delay {
+            cap_header_extensions.push(RtpHeaderExtension {
+                kind: Some(MediaKind::Video),
+                uri: RtpHeaderExtensionUri::PlayoutDelay,
+                preferred_id: 13,
+                preferred_encrypt: false,
+                direction: RtpHeaderExtensionDirection::SendRecv,
+            });
+        }
+
+        let caps = RtpCapabilities {
+            codecs: vec![RtpCodecCapabilityFinalized::Video {
+                mime_type: MimeTypeVideo::VP8,
+                preferred_payload_type: 101,
+                clock_rate: NonZeroU32::new(90000).unwrap(),
+                parameters: RtpCodecParametersParameters::default(),
+                rtcp_feedback: vec![],
+            }],
+            header_extensions: cap_header_extensions,
+            fec_mechanisms: vec![],
+        };
+
+        (consumable_params, caps)
+    }
+
+    #[test]
+    fn get_consumer_rtp_parameters_negotiates_playout_delay_when_both_sides_advertise_it() {
+        let (consumable_params, caps) = consumable_params_and_caps_with_playout_delay(true);
+
+        let requested_playout_delay = PlayoutDelay { min: 0, max: 100 };
+
+        let (_, playout_delay) = get_consumer_rtp_parameters(
+            &consumable_params,
+            caps,
+            None,
+            Some(requested_playout_delay),
+        )
+        .expect("Failed to get consumer RTP parameters");
+
+        assert_eq!(playout_delay, Some(requested_playout_delay));
+    }
+
+    #[test]
+    fn get_consumer_rtp_parameters_drops_playout_delay_when_not_advertised() {
+        let (consumable_params, caps) = consumable_params_and_caps_with_playout_delay(false);
+
+        let requested_playout_delay = PlayoutDelay { min: 0, max: 100 };
+
+        let (_, playout_delay) = get_consumer_rtp_parameters(
+            &consumable_params,
+            caps,
+            None,
+            Some(requested_playout_delay),
+        )
+        .expect("Failed to get consumer RTP parameters");
+
+        assert_eq!(playout_delay, None);
+    }
+
+    #[test]
+    fn get_producer_rtp_parameters_mapping_svc_single_encoding_maps_one_ssrc_per_spatial_layer() {
+        let media_codecs = vec![RtpCodecCapability::Video {
+            mime_type: MimeTypeVideo::VP9,
+            preferred_payload_type: None,
+            clock_rate: NonZeroU32::new(90000).unwrap(),
+            parameters: RtpCodecParametersParameters::default(),
+            rtcp_feedback: vec![],
+        }];
+
+        let router_rtp_capabilities = generate_router_rtp_capabilities(media_codecs)
+            .expect("Failed to generate router RTP capabilities");
+
+        // A single SVC encoding describing 3 spatial layers (`L3T3`) must still be mapped to 3
+        // distinct SSRCs/consumable encodings, the same way 3 simulcast encodings would be, so a
+        // Consumer can select/switch between spatial layers independently.
+        let rtp_parameters = RtpParameters {
+            mid: None,
+            codecs: vec![RtpCodecParameters::Video {
+                mime_type: MimeTypeVideo::VP9,
+                payload_type: 100,
+                clock_rate: NonZeroU32::new(90000).unwrap(),
+                parameters: RtpCodecParametersParameters::default(),
+                rtcp_feedback: vec![],
+            }],
+            header_extensions: vec![],
+            encodings: vec![RtpEncodingParameters {
+                ssrc: Some(11111111),
+                scalability_mode: Some("L3T3".to_string()),
+                ..RtpEncodingParameters::default()
+            }],
+            rtcp: RtcpParameters::default(),
+        };
+
+        let rtp_mapping =
+            get_producer_rtp_parameters_mapping(&rtp_parameters, &router_rtp_capabilities)
+                .expect("Failed to get producer RTP parameters mapping");
+
+        assert_eq!(rtp_mapping.encodings.len(), 3);
+        let mapped_ssrcs: std::collections::HashSet<_> = rtp_mapping
+            .encodings
+            .iter()
+            .map(|encoding| encoding.mapped_ssrc)
+            .collect();
+        assert_eq!(mapped_ssrcs.len(), 3);
+
+        let consumable_rtp_parameters = get_consumable_rtp_parameters(
+            MediaKind::Video,
+            &rtp_parameters,
+            &router_rtp_capabilities,
+            &rtp_mapping,
+        );
+
+        assert_eq!(consumable_rtp_parameters.encodings.len(), 3);
+        let consumable_ssrcs: std::collections::HashSet<_> = consumable_rtp_parameters
+            .encodings
+            .iter()
+            .map(|encoding| encoding.ssrc)
+            .collect();
+        assert_eq!(consumable_ssrcs.len(), 3);
+    }
+
+    #[test]
+    fn scalability_mode_parses_full_av1_svc_string_set() {
+        // Every scalability mode this series' SVC mapping (`get_producer_rtp_parameters_mapping`)
+        // needs to recognize, including the AV1-specific `_KEY`/`_KEY_SHIFT` inter-layer
+        // prediction suffixes, must parse rather than silently falling back to 1 spatial layer.
+        for mode in [
+            "L1T1", "L1T2", "L1T3", "L2T1", "L2T2", "L2T3", "L3T1", "L3T2", "L3T3", "L2T3_KEY",
+            "L3T3_KEY", "L3T3_KEY_SHIFT",
+        ] {
+            assert!(
+                mode.parse::<ScalabilityMode>().is_ok(),
+                "failed to parse scalability mode {mode}",
+            );
+        }
+    }
+
     #[test]
     fn get_producer_rtp_parameters_mapping_unsupported() {
         let media_codecs = vec![
@@ -0,0 +1,66 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// A wire-serialization format for messages exchanged with the worker process.
+///
+/// Implementations only convert between a Rust value and the bytes that cross the channel; they
+/// make no assumption about transport. `WorkerSettings` picks one per worker, so high-frequency
+/// payloads (periodic `getStats`, RTP score updates, audio-level observer notifications) can trade
+/// JSON's debuggability for a more compact encoding without any call site caring which is in use.
+pub trait PayloadCodec: Send + Sync + 'static {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    fn serialize<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, Self::Error>;
+
+    fn deserialize<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, Self::Error>;
+}
+
+/// The default codec: JSON, matching the worker's native payload format. Every serde-derived
+/// type in this crate already satisfies [`PayloadCodec`]'s bounds, so this is a drop-in.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct JsonPayloadCodec;
+
+impl PayloadCodec for JsonPayloadCodec {
+    type Error = serde_json::Error;
+
+    fn serialize<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, Self::Error> {
+        serde_json::to_vec(value)
+    }
+
+    fn deserialize<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, Self::Error> {
+        serde_json::from_slice(bytes)
+    }
+}
+
+// A `bincode`-backed `PayloadCodec` was sketched out alongside this trait for deployments that
+// want a more compact wire format than JSON. It's been pulled for now: nothing in this crate
+// picks a codec per worker yet (that wiring belongs in `WorkerSettings`/`WorkerManager::create_worker`,
+// neither of which exists in this tree), and a `#[cfg(feature = "bincode")]` item with no
+// `Cargo.toml` declaring that feature can never actually compile in. Re-add it once
+// `WorkerSettings` grows a codec field that can select it.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, Serialize, PartialEq)]
+    struct Sample {
+        foo: u32,
+        bar: String,
+    }
+
+    #[test]
+    fn json_payload_codec_round_trips() {
+        let codec = JsonPayloadCodec;
+        let value = Sample {
+            foo: 42,
+            bar: "baz".to_string(),
+        };
+
+        let bytes = codec.serialize(&value).expect("Failed to serialize");
+        let decoded: Sample = codec.deserialize(&bytes).expect("Failed to deserialize");
+
+        assert_eq!(decoded, value);
+    }
+}
@@ -0,0 +1,37 @@
+use crate::data_structures::{AppData, IceRole};
+use crate::webrtc_transport::TransportListenIps;
+
+// NOTE: this only covers the options struct. Wiring it up end to end (a `Router::create_quic_transport`
+// constructor producing something that implements the `Transport` trait, backed by
+// `QuicParameters`/`QuicState` from `data_structures.rs`) lives in `router.rs`/`transport.rs`, neither
+// of which is part of this tree. Land that wiring before advertising QUIC transports as usable.
+
+/// Options for creating a QUIC-based data transport, the unordered/partially-reliable alternative
+/// to [`WebRtcTransport`](crate::webrtc_transport::WebRtcTransport)'s SCTP-over-DTLS association.
+#[derive(Debug, Clone)]
+pub struct QuicTransportOptions {
+    /// Listen IPs in order of preference (first one is the preferred one).
+    pub listen_ips: TransportListenIps,
+    /// Initial available outgoing bitrate (in bps).
+    pub initial_available_outgoing_bitrate: u32,
+    /// Create a SCTP association, if absent defaults to `false` since QUIC streams replace it.
+    pub enable_sctp: bool,
+    /// ICE role. Only `IceRole::Controlled` makes sense for a server-side endpoint.
+    pub ice_role: IceRole,
+    /// Custom application data.
+    pub app_data: AppData,
+}
+
+impl QuicTransportOptions {
+    /// Create options with the given listen IPs and defaults for everything else, matching
+    /// [`WebRtcTransportOptions::new`](crate::webrtc_transport::WebRtcTransportOptions::new).
+    pub fn new(listen_ips: TransportListenIps) -> Self {
+        Self {
+            listen_ips,
+            initial_available_outgoing_bitrate: 600_000,
+            enable_sctp: false,
+            ice_role: IceRole::Controlled,
+            app_data: AppData::default(),
+        }
+    }
+}
@@ -0,0 +1,933 @@
+//! Conversion between SDP media sections and this crate's `RtpParameters`/`RtpCapabilities`
+//! types, so integrators that don't go through `mediasoup-client` can exchange plain SDP with a
+//! Router.
+
+use crate::data_structures::{
+    IceCandidate, IceCandidateTcpType, IceCandidateType, IceParameters, TransportProtocol,
+};
+use crate::ortc::{
+    is_red, match_codecs, parse_max_framerate, static_payload_type_defaults, CodecToMatch,
+};
+use crate::rtp_parameters::{
+    MediaKind, MimeType, RtcpFeedback, RtcpParameters, RtpCodecParameters,
+    RtpCodecParametersParameters, RtpEncodingParameters, RtpEncodingParametersRtx,
+    RtpHeaderExtensionParameters, RtpHeaderExtensionUri, RtpParameters,
+};
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::num::{NonZeroU32, NonZeroU8};
+use thiserror::Error;
+
+/// Error caused by a malformed or unsupported SDP media section.
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum SdpParseError {
+    /// The `m=` line is missing or malformed.
+    #[error("Missing or invalid m= line")]
+    InvalidMediaLine,
+    /// An `a=rtpmap` line could not be parsed.
+    #[error("Invalid rtpmap line: {0}")]
+    InvalidRtpMap(String),
+    /// The encoding name in a `rtpmap` line is not a known mime type.
+    #[error("Unknown encoding name {0}")]
+    UnknownEncodingName(String),
+}
+
+/// Parse the `a=rtpmap:<payload_type> <name>/<clock_rate>[/<channels>]` line body (everything
+/// after `a=rtpmap:`) into `(payload_type, encoding_name, clock_rate, channels)`.
+fn parse_rtpmap(kind: MediaKind, body: &str) -> Result<(u8, String, NonZeroU32, Option<NonZeroU8>), SdpParseError> {
+    let (pt, rest) = body
+        .split_once(' ')
+        .ok_or_else(|| SdpParseError::InvalidRtpMap(body.to_string()))?;
+    let payload_type: u8 = pt
+        .trim()
+        .parse()
+        .map_err(|_| SdpParseError::InvalidRtpMap(body.to_string()))?;
+
+    let mut parts = rest.trim().splitn(3, '/');
+    let encoding_name = parts
+        .next()
+        .ok_or_else(|| SdpParseError::InvalidRtpMap(body.to_string()))?
+        .to_string();
+    let clock_rate: NonZeroU32 = parts
+        .next()
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| SdpParseError::InvalidRtpMap(body.to_string()))?;
+    // Audio encodings default to mono when the channel count is omitted, per RFC 4566.
+    let channels = match kind {
+        MediaKind::Audio => Some(
+            parts
+                .next()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or_else(|| NonZeroU8::new(1).unwrap()),
+        ),
+        MediaKind::Video => None,
+    };
+
+    Ok((payload_type, encoding_name, clock_rate, channels))
+}
+
+/// Render one `key`/`value` fmtp pair for `mime_type` as it should appear in an `a=fmtp:` line.
+/// RED's fmtp is a bare ordered PT list (e.g. `"111/111"`) with no `key=value` shape; on the way in
+/// `parse_fmtp` stores it as a parameter whose key *is* that list and whose value is a placeholder
+/// (see below), so on the way out it must be rendered bare rather than as `111/111=1`.
+fn format_fmtp_pair(mime_type: MimeType, key: &str, value: impl std::fmt::Display) -> String {
+    if is_red(mime_type) {
+        key.to_string()
+    } else {
+        format!("{key}={value}")
+    }
+}
+
+/// Parse an `a=fmtp:<payload_type> <key>=<value>;...` line body into its key/value parameters.
+fn parse_fmtp(body: &str) -> Option<(u8, RtpCodecParametersParameters)> {
+    let (pt, rest) = body.split_once(' ')?;
+    let payload_type: u8 = pt.trim().parse().ok()?;
+
+    let mut parameters = RtpCodecParametersParameters::new();
+    for pair in rest.split(';') {
+        let pair = pair.trim();
+        if pair.is_empty() {
+            continue;
+        }
+        match pair.split_once('=') {
+            Some((key, value)) => {
+                let parsed_value = match value.parse::<u32>() {
+                    Ok(number) => number.into(),
+                    Err(_) => value.to_string().into(),
+                };
+                parameters.insert(key, parsed_value);
+            }
+            // Valueless fmtp tokens (e.g. RED's `"111/111"`) are stored with a placeholder value.
+            None => {
+                parameters.insert(pair, 1u32.into());
+            }
+        }
+    }
+
+    Some((payload_type, parameters))
+}
+
+/// Parse an `a=rtcp-fb:<payload_type> <type>[ <parameter>]` line body into its feedback type.
+/// Unknown feedback tokens map to [`RtcpFeedback::Unsupported`] rather than failing the parse.
+fn parse_rtcp_fb(body: &str) -> Option<(u8, RtcpFeedback)> {
+    let (pt, rest) = body.split_once(' ')?;
+    let payload_type: u8 = pt.trim().parse().ok()?;
+
+    let feedback = match rest.trim() {
+        "nack" => RtcpFeedback::Nack,
+        "nack pli" => RtcpFeedback::NackPli,
+        "ccm fir" => RtcpFeedback::CcmFir,
+        "goog-remb" => RtcpFeedback::GoogRemb,
+        "transport-cc" => RtcpFeedback::TransportCC,
+        _ => RtcpFeedback::Unsupported,
+    };
+
+    Some((payload_type, feedback))
+}
+
+/// Parse an `a=extmap:<id>[/<direction>] <uri>` line body into `(id, uri)`. Unknown URIs are
+/// skipped by the caller rather than rejecting the whole media section.
+fn parse_extmap(body: &str) -> Option<(u16, RtpHeaderExtensionUri)> {
+    let (id_and_direction, uri) = body.split_once(' ')?;
+    let id: u16 = id_and_direction
+        .split('/')
+        .next()?
+        .trim()
+        .parse()
+        .ok()?;
+    let uri = rtp_header_extension_uri_from_str(uri.trim())?;
+
+    Some((id, uri))
+}
+
+fn rtp_header_extension_uri_from_str(uri: &str) -> Option<RtpHeaderExtensionUri> {
+    match uri {
+        "urn:ietf:params:rtp-hdrext:sdes:mid" => Some(RtpHeaderExtensionUri::MID),
+        "urn:3gpp:video-orientation" => Some(RtpHeaderExtensionUri::VideoOrientation),
+        "http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01" => {
+            Some(RtpHeaderExtensionUri::TransportWideCCDraft01)
+        }
+        "http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time" => {
+            Some(RtpHeaderExtensionUri::AbsSendTime)
+        }
+        "http://www.webrtc.org/experiments/rtp-hdrext/playout-delay" => {
+            Some(RtpHeaderExtensionUri::PlayoutDelay)
+        }
+        _ => None,
+    }
+}
+
+/// Parse the media lines of a single `m=` section (not including the `m=` line itself) into
+/// `RtpParameters`, falling back to sane defaults when `a=rtpmap` is missing for a payload type.
+pub(crate) fn rtp_parameters_from_media_lines(
+    kind: MediaKind,
+    lines: &[&str],
+) -> Result<RtpParameters, SdpParseError> {
+    let mut rtpmaps = BTreeMap::<u8, (String, NonZeroU32, Option<NonZeroU8>)>::new();
+    let mut fmtps = BTreeMap::<u8, RtpCodecParametersParameters>::new();
+    let mut rtcp_fbs = BTreeMap::<u8, Vec<RtcpFeedback>>::new();
+    let mut header_extensions = Vec::new();
+    let mut mid = None;
+    let mut rid = None;
+    let mut ssrc = None;
+    let mut rtx_ssrc = None;
+    let mut simulcast_rids = Vec::new();
+    let mut max_framerate = None;
+
+    for line in lines {
+        let line = line.trim();
+        if let Some(body) = line.strip_prefix("a=rtpmap:") {
+            let (pt, name, clock_rate, channels) = parse_rtpmap(kind, body)?;
+            rtpmaps.insert(pt, (name, clock_rate, channels));
+        } else if let Some(body) = line.strip_prefix("a=fmtp:") {
+            if let Some((pt, parameters)) = parse_fmtp(body) {
+                fmtps.insert(pt, parameters);
+            }
+        } else if let Some(body) = line.strip_prefix("a=rtcp-fb:") {
+            if let Some((pt, feedback)) = parse_rtcp_fb(body) {
+                rtcp_fbs.entry(pt).or_default().push(feedback);
+            }
+        } else if let Some(body) = line.strip_prefix("a=extmap:") {
+            if let Some((id, uri)) = parse_extmap(body) {
+                header_extensions.push(RtpHeaderExtensionParameters {
+                    uri,
+                    id,
+                    encrypt: false,
+                });
+            }
+        } else if let Some(body) = line.strip_prefix("a=mid:") {
+            mid = Some(body.trim().to_string());
+        } else if let Some(body) = line.strip_prefix("a=rid:") {
+            rid = body.split_whitespace().next().map(|s| s.to_string());
+        } else if let Some(body) = line.strip_prefix("a=ssrc-group:FID ") {
+            // `a=ssrc-group:FID <primary-ssrc> <rtx-ssrc>` pairs a media SSRC with its RTX SSRC.
+            let mut ssrcs = body.split_whitespace().filter_map(|s| s.parse::<u32>().ok());
+            ssrc = ssrc.or(ssrcs.next());
+            rtx_ssrc = ssrcs.next();
+        } else if let Some(body) = line.strip_prefix("a=ssrc:") {
+            ssrc = ssrc.or_else(|| {
+                body.split_whitespace()
+                    .next()
+                    .and_then(|s| s.parse::<u32>().ok())
+            });
+        } else if let Some(body) = line.strip_prefix("a=framerate:") {
+            // Accepts both a plain fps integer and a cast-style (openscreen) rational like
+            // `"60000/1000"`.
+            max_framerate = parse_max_framerate(body.trim());
+        } else if let Some(body) = line.strip_prefix("a=simulcast:") {
+            // `a=simulcast:send <rid1>;<rid2>;...` (recv variants are not produced by this crate).
+            if let Some((_direction, rids)) = body.trim().split_once(' ') {
+                simulcast_rids = rids
+                    .split(';')
+                    .map(|rid| rid.trim_start_matches('~').to_string())
+                    .filter(|rid| !rid.is_empty())
+                    .collect();
+            }
+        }
+    }
+
+    // RFC 4566 allows an endpoint to omit `a=rtpmap` for the classic RFC 3551 static payload
+    // types, so backfill one from `ortc`'s static payload-type table for any payload type that
+    // only showed up in `a=fmtp`/`a=rtcp-fb` lines.
+    for payload_type in fmtps.keys().chain(rtcp_fbs.keys()).copied().collect::<Vec<_>>() {
+        if let Entry::Vacant(entry) = rtpmaps.entry(payload_type) {
+            if let Some((default_kind, mime_type, clock_rate, _)) =
+                static_payload_type_defaults(payload_type)
+            {
+                if default_kind == kind {
+                    let (name, channels) = encoding_name_of(mime_type);
+                    entry.insert((name.to_string(), clock_rate, channels));
+                }
+            }
+        }
+    }
+
+    let mut codecs = Vec::new();
+    for (&payload_type, (encoding_name, clock_rate, channels)) in rtpmaps.iter() {
+        let mime_type = mime_type_from_encoding_name(kind, encoding_name)
+            .ok_or_else(|| SdpParseError::UnknownEncodingName(encoding_name.clone()))?;
+        let parameters = fmtps.remove(&payload_type).unwrap_or_default();
+        let rtcp_feedback = rtcp_fbs.remove(&payload_type).unwrap_or_default();
+
+        codecs.push(match mime_type {
+            MimeType::Audio(mime_type) => RtpCodecParameters::Audio {
+                mime_type,
+                payload_type,
+                clock_rate: *clock_rate,
+                channels: channels.unwrap_or_else(|| NonZeroU8::new(1).unwrap()),
+                parameters,
+                rtcp_feedback,
+            },
+            MimeType::Video(mime_type) => RtpCodecParameters::Video {
+                mime_type,
+                payload_type,
+                clock_rate: *clock_rate,
+                parameters,
+                rtcp_feedback,
+            },
+        });
+    }
+
+    let rtx = rtx_ssrc.map(|ssrc| RtpEncodingParametersRtx { ssrc });
+
+    let mut encodings = Vec::new();
+    if !simulcast_rids.is_empty() {
+        // `a=simulcast` describes one encoding per rid; none of them carry an individual ssrc.
+        encodings.extend(simulcast_rids.into_iter().map(|rid| RtpEncodingParameters {
+            rid: Some(rid),
+            max_framerate,
+            ..RtpEncodingParameters::default()
+        }));
+    } else if ssrc.is_some() || rid.is_some() {
+        encodings.push(RtpEncodingParameters {
+            ssrc,
+            rid,
+            rtx,
+            max_framerate,
+            ..RtpEncodingParameters::default()
+        });
+    }
+
+    Ok(RtpParameters {
+        mid,
+        codecs,
+        header_extensions,
+        encodings,
+        rtcp: RtcpParameters::default(),
+    })
+}
+
+/// Map a `rtpmap` encoding name (case-insensitive, as it appears on the wire) to this crate's
+/// `MimeType`. Returns `None` for unrecognized names so the caller can decide whether that is a
+/// hard error or something to skip.
+fn mime_type_from_encoding_name(kind: MediaKind, name: &str) -> Option<MimeType> {
+    use crate::rtp_parameters::{MimeTypeAudio, MimeTypeVideo};
+
+    Some(match kind {
+        MediaKind::Audio => MimeType::Audio(match name.to_ascii_uppercase().as_str() {
+            "OPUS" => MimeTypeAudio::Opus,
+            "PCMU" => MimeTypeAudio::Pcmu,
+            "PCMA" => MimeTypeAudio::Pcma,
+            "GSM" => MimeTypeAudio::Gsm,
+            "G723" => MimeTypeAudio::G723,
+            "G722" => MimeTypeAudio::G722,
+            "CN" => MimeTypeAudio::CN,
+            "RED" => MimeTypeAudio::RED,
+            "TELEPHONE-EVENT" => MimeTypeAudio::TelephoneEvent,
+            "MP4A-LATM" => MimeTypeAudio::Mp4aLatm,
+            "MPEG4-GENERIC" => MimeTypeAudio::Mpeg4Generic,
+            _ => return None,
+        }),
+        MediaKind::Video => MimeType::Video(match name.to_ascii_uppercase().as_str() {
+            "VP8" => MimeTypeVideo::VP8,
+            "VP9" => MimeTypeVideo::VP9,
+            "H264" => MimeTypeVideo::H264,
+            "RTX" => MimeTypeVideo::RTX,
+            "AV1" => MimeTypeVideo::AV1,
+            "ULPFEC" => MimeTypeVideo::ULPFEC,
+            "FLEXFEC-03" => MimeTypeVideo::FLEXFEC,
+            _ => return None,
+        }),
+    })
+}
+
+/// Generate the `a=rtpmap`/`a=fmtp`/`a=rtcp-fb`/`a=extmap`/`a=mid`/`a=ssrc` attribute lines for a
+/// media section carrying `rtp_parameters`.
+pub(crate) fn media_lines_from_rtp_parameters(rtp_parameters: &RtpParameters) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    if let Some(mid) = &rtp_parameters.mid {
+        lines.push(format!("a=mid:{mid}"));
+    }
+
+    for codec in rtp_parameters.codecs.iter() {
+        let (name, _) = encoding_name_of(codec.mime_type());
+        let channels = match codec {
+            RtpCodecParameters::Audio { channels, .. } => Some(*channels),
+            RtpCodecParameters::Video { .. } => None,
+        };
+        let clock_rate = codec.clock_rate();
+
+        match channels {
+            Some(channels) if channels.get() != 1 => lines.push(format!(
+                "a=rtpmap:{} {name}/{clock_rate}/{channels}",
+                codec.payload_type()
+            )),
+            _ => lines.push(format!(
+                "a=rtpmap:{} {name}/{clock_rate}",
+                codec.payload_type()
+            )),
+        }
+
+        if !codec.parameters().is_empty() {
+            let params = codec
+                .parameters()
+                .iter()
+                .map(|(key, value)| format_fmtp_pair(codec.mime_type(), key, value))
+                .collect::<Vec<_>>()
+                .join(";");
+            lines.push(format!("a=fmtp:{} {params}", codec.payload_type()));
+        }
+
+        for feedback in codec.rtcp_feedback() {
+            lines.push(format!(
+                "a=rtcp-fb:{} {}",
+                codec.payload_type(),
+                rtcp_feedback_token(feedback)
+            ));
+        }
+    }
+
+    for ext in rtp_parameters.header_extensions.iter() {
+        if let Some(uri) = rtp_header_extension_uri_to_str(ext.uri) {
+            lines.push(format!("a=extmap:{} {uri}", ext.id));
+        }
+    }
+
+    let mut rids = Vec::new();
+    for encoding in rtp_parameters.encodings.iter() {
+        let cname = rtp_parameters.rtcp.cname.clone().unwrap_or_default();
+        if let Some(ssrc) = encoding.ssrc {
+            lines.push(format!("a=ssrc:{ssrc} cname:{cname}"));
+            if let Some(rtx) = &encoding.rtx {
+                lines.push(format!("a=ssrc:{} cname:{cname}", rtx.ssrc));
+                lines.push(format!("a=ssrc-group:FID {ssrc} {}", rtx.ssrc));
+            }
+        }
+        if let Some(rid) = &encoding.rid {
+            // This function only ever describes RTP parameters a local Producer sends, so the rid
+            // (and the `a=simulcast:` line below it) is always a "send" direction from this
+            // endpoint's perspective, matching the `a=simulcast:send` this crate emits; there is no
+            // "recv" case to scope this on.
+            lines.push(format!("a=rid:{rid} send"));
+            rids.push(rid.clone());
+        }
+    }
+    if !rids.is_empty() {
+        lines.push(format!("a=simulcast:send {}", rids.join(";")));
+    }
+
+    lines
+}
+
+/// Match each codec `offered` by a remote peer against the codecs this side `supports`, reusing
+/// [`match_codecs`] (and, transitively, the H264 `profile-level-id`/AV1 `level-idx` negotiation it
+/// performs) to decide which offered payload types are usable and what negotiated parameter, if
+/// any, the answer must carry. Codecs that don't match anything supported are dropped.
+pub(crate) fn answer_media_lines_from_offer(
+    offered: &RtpParameters,
+    supported: &RtpParameters,
+) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    if let Some(mid) = &offered.mid {
+        lines.push(format!("a=mid:{mid}"));
+    }
+
+    for offered_codec in offered.codecs.iter() {
+        let matched = supported.codecs.iter().find_map(|supported_codec| {
+            match_codecs(
+                CodecToMatch::from(offered_codec),
+                CodecToMatch::from(supported_codec),
+                true,
+            )
+            .ok()
+        });
+        let negotiated = match matched {
+            Some(negotiated) => negotiated,
+            None => continue,
+        };
+
+        let (name, channels) = encoding_name_of(offered_codec.mime_type());
+        let clock_rate = offered_codec.clock_rate();
+        let payload_type = offered_codec.payload_type();
+
+        match channels {
+            Some(channels) if channels.get() != 1 => lines.push(format!(
+                "a=rtpmap:{payload_type} {name}/{clock_rate}/{channels}"
+            )),
+            _ => lines.push(format!("a=rtpmap:{payload_type} {name}/{clock_rate}")),
+        }
+
+        let mut parameters = offered_codec.parameters().clone();
+        if let Some((key, value)) = negotiated {
+            parameters.insert(key, value.into());
+        }
+        if !parameters.is_empty() {
+            let params = parameters
+                .iter()
+                .map(|(key, value)| format_fmtp_pair(offered_codec.mime_type(), key, value))
+                .collect::<Vec<_>>()
+                .join(";");
+            lines.push(format!("a=fmtp:{payload_type} {params}"));
+        }
+
+        for feedback in offered_codec.rtcp_feedback() {
+            lines.push(format!(
+                "a=rtcp-fb:{payload_type} {}",
+                rtcp_feedback_token(feedback)
+            ));
+        }
+    }
+
+    lines
+}
+
+fn encoding_name_of(mime_type: MimeType) -> (&'static str, Option<NonZeroU8>) {
+    use crate::rtp_parameters::{MimeTypeAudio, MimeTypeVideo};
+
+    match mime_type {
+        MimeType::Audio(MimeTypeAudio::Opus) => ("opus", NonZeroU8::new(2)),
+        MimeType::Audio(MimeTypeAudio::Pcmu) => ("PCMU", NonZeroU8::new(1)),
+        MimeType::Audio(MimeTypeAudio::Pcma) => ("PCMA", NonZeroU8::new(1)),
+        MimeType::Audio(MimeTypeAudio::Gsm) => ("GSM", NonZeroU8::new(1)),
+        MimeType::Audio(MimeTypeAudio::G723) => ("G723", NonZeroU8::new(1)),
+        MimeType::Audio(MimeTypeAudio::G722) => ("G722", NonZeroU8::new(1)),
+        MimeType::Audio(MimeTypeAudio::CN) => ("CN", NonZeroU8::new(1)),
+        MimeType::Audio(MimeTypeAudio::RED) => ("red", NonZeroU8::new(1)),
+        MimeType::Audio(MimeTypeAudio::TelephoneEvent) => ("telephone-event", NonZeroU8::new(1)),
+        MimeType::Audio(MimeTypeAudio::Mp4aLatm) => ("MP4A-LATM", NonZeroU8::new(1)),
+        MimeType::Audio(MimeTypeAudio::Mpeg4Generic) => ("mpeg4-generic", NonZeroU8::new(1)),
+        MimeType::Video(MimeTypeVideo::VP8) => ("VP8", None),
+        MimeType::Video(MimeTypeVideo::VP9) => ("VP9", None),
+        MimeType::Video(MimeTypeVideo::H264) => ("H264", None),
+        MimeType::Video(MimeTypeVideo::RTX) => ("rtx", None),
+        MimeType::Video(MimeTypeVideo::AV1) => ("AV1", None),
+        MimeType::Video(MimeTypeVideo::ULPFEC) => ("ulpfec", None),
+        MimeType::Video(MimeTypeVideo::FLEXFEC) => ("flexfec-03", None),
+        _ => ("unknown", None),
+    }
+}
+
+fn rtcp_feedback_token(feedback: &RtcpFeedback) -> &'static str {
+    match feedback {
+        RtcpFeedback::Nack => "nack",
+        RtcpFeedback::NackPli => "nack pli",
+        RtcpFeedback::CcmFir => "ccm fir",
+        RtcpFeedback::GoogRemb => "goog-remb",
+        RtcpFeedback::TransportCC => "transport-cc",
+        RtcpFeedback::Unsupported => "unsupported",
+    }
+}
+
+fn rtp_header_extension_uri_to_str(uri: RtpHeaderExtensionUri) -> Option<&'static str> {
+    match uri {
+        RtpHeaderExtensionUri::MID => Some("urn:ietf:params:rtp-hdrext:sdes:mid"),
+        RtpHeaderExtensionUri::VideoOrientation => Some("urn:3gpp:video-orientation"),
+        RtpHeaderExtensionUri::TransportWideCCDraft01 => {
+            Some("http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01")
+        }
+        RtpHeaderExtensionUri::AbsSendTime => {
+            Some("http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time")
+        }
+        RtpHeaderExtensionUri::PlayoutDelay => {
+            Some("http://www.webrtc.org/experiments/rtp-hdrext/playout-delay")
+        }
+        _ => None,
+    }
+}
+
+impl IceCandidate {
+    /// Render this candidate as the body of an `a=candidate:` SDP attribute line (everything
+    /// after `a=candidate:`), for peers that negotiate over plain SDP instead of
+    /// mediasoup-client's transport parameters object. mediasoup always multiplexes RTP/RTCP, so
+    /// the RFC 5245 component id is hardcoded to `1`.
+    pub fn to_sdp_attribute(&self) -> String {
+        let protocol = transport_protocol_to_str(self.protocol);
+        let typ = ice_candidate_type_to_str(self.r#type);
+
+        let mut attribute = format!(
+            "{} 1 {} {} {} {} typ {}",
+            self.foundation, protocol, self.priority, self.ip, self.port, typ,
+        );
+
+        if self.tcp_type == Some(IceCandidateTcpType::Passive) {
+            attribute.push_str(" tcptype passive");
+        }
+
+        attribute
+    }
+
+    /// Parse the body of a remote peer's `a=candidate:` SDP attribute line (everything after
+    /// `a=candidate:`) back into an `IceCandidate`.
+    pub fn from_sdp_attribute(body: &str) -> Option<Self> {
+        let mut tokens = body.split_whitespace();
+
+        let foundation = tokens.next()?.to_string();
+        let _component_id: u16 = tokens.next()?.parse().ok()?;
+        let protocol = transport_protocol_from_str(tokens.next()?)?;
+        let priority: u32 = tokens.next()?.parse().ok()?;
+        let ip = tokens.next()?.parse().ok()?;
+        let port: u16 = tokens.next()?.parse().ok()?;
+
+        if tokens.next()? != "typ" {
+            return None;
+        }
+        let r#type = ice_candidate_type_from_str(tokens.next()?)?;
+
+        let mut tcp_type = None;
+        while let Some(token) = tokens.next() {
+            if token == "tcptype" {
+                tcp_type = match tokens.next()? {
+                    "passive" => Some(IceCandidateTcpType::Passive),
+                    _ => return None,
+                };
+            }
+        }
+
+        Some(IceCandidate {
+            foundation,
+            priority,
+            ip,
+            protocol,
+            port,
+            r#type,
+            tcp_type,
+        })
+    }
+}
+
+impl IceParameters {
+    /// Render the `a=ice-ufrag:`/`a=ice-pwd:` (and, for an ICE-lite transport, `a=ice-lite`) SDP
+    /// attribute lines for these ICE parameters.
+    pub fn to_sdp_lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("a=ice-ufrag:{}", self.username_fragment),
+            format!("a=ice-pwd:{}", self.password),
+        ];
+
+        if self.ice_lite == Some(true) {
+            lines.push("a=ice-lite".to_string());
+        }
+
+        lines
+    }
+}
+
+fn transport_protocol_to_str(protocol: TransportProtocol) -> &'static str {
+    match protocol {
+        TransportProtocol::Udp => "udp",
+        TransportProtocol::Tcp => "tcp",
+    }
+}
+
+fn transport_protocol_from_str(protocol: &str) -> Option<TransportProtocol> {
+    match protocol.to_lowercase().as_str() {
+        "udp" => Some(TransportProtocol::Udp),
+        "tcp" => Some(TransportProtocol::Tcp),
+        _ => None,
+    }
+}
+
+fn ice_candidate_type_to_str(candidate_type: IceCandidateType) -> &'static str {
+    match candidate_type {
+        IceCandidateType::Host => "host",
+        IceCandidateType::Srflx => "srflx",
+        IceCandidateType::Prflx => "prflx",
+        IceCandidateType::Relay => "relay",
+    }
+}
+
+fn ice_candidate_type_from_str(candidate_type: &str) -> Option<IceCandidateType> {
+    match candidate_type {
+        "host" => Some(IceCandidateType::Host),
+        "srflx" => Some(IceCandidateType::Srflx),
+        "prflx" => Some(IceCandidateType::Prflx),
+        "relay" => Some(IceCandidateType::Relay),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rtp_parameters::MimeTypeAudio;
+
+    #[test]
+    fn rtp_parameters_from_media_lines_parses_rtpmap_fmtp_and_ssrc() {
+        let lines = [
+            "a=rtpmap:111 opus/48000/2",
+            "a=fmtp:111 useinbandfec=1",
+            "a=rtcp-fb:111 transport-cc",
+            "a=mid:0",
+            "a=ssrc-group:FID 1111 2222",
+            "a=ssrc:1111 cname:test",
+        ];
+
+        let rtp_parameters =
+            rtp_parameters_from_media_lines(MediaKind::Audio, &lines).expect("Failed to parse");
+
+        assert_eq!(rtp_parameters.mid.as_deref(), Some("0"));
+        assert_eq!(
+            rtp_parameters.codecs,
+            vec![RtpCodecParameters::Audio {
+                mime_type: MimeTypeAudio::Opus,
+                payload_type: 111,
+                clock_rate: NonZeroU32::new(48000).unwrap(),
+                channels: NonZeroU8::new(2).unwrap(),
+                parameters: RtpCodecParametersParameters::from([("useinbandfec", 1u32.into())]),
+                rtcp_feedback: vec![RtcpFeedback::TransportCC],
+            }]
+        );
+        assert_eq!(rtp_parameters.encodings.len(), 1);
+        assert_eq!(rtp_parameters.encodings[0].ssrc, Some(1111));
+        assert_eq!(
+            rtp_parameters.encodings[0]
+                .rtx
+                .as_ref()
+                .map(|rtx| rtx.ssrc),
+            Some(2222)
+        );
+    }
+
+    #[test]
+    fn rtp_parameters_from_media_lines_parses_cast_style_framerate() {
+        let lines = [
+            "a=rtpmap:96 VP8/90000",
+            "a=ssrc:1111 cname:test",
+            "a=framerate:60000/1000",
+        ];
+
+        let rtp_parameters =
+            rtp_parameters_from_media_lines(MediaKind::Video, &lines).expect("Failed to parse");
+
+        assert_eq!(rtp_parameters.encodings[0].max_framerate, Some(60));
+    }
+
+    #[test]
+    fn rtp_parameters_from_media_lines_falls_back_to_static_payload_type() {
+        // No `a=rtpmap:0`: PT 0 is the classic static PCMU assignment.
+        let lines = ["a=rtcp-fb:0 nack"];
+
+        let rtp_parameters =
+            rtp_parameters_from_media_lines(MediaKind::Audio, &lines).expect("Failed to parse");
+
+        assert_eq!(
+            rtp_parameters.codecs,
+            vec![RtpCodecParameters::Audio {
+                mime_type: MimeTypeAudio::Pcmu,
+                payload_type: 0,
+                clock_rate: NonZeroU32::new(8000).unwrap(),
+                channels: NonZeroU8::new(1).unwrap(),
+                parameters: RtpCodecParametersParameters::new(),
+                rtcp_feedback: vec![RtcpFeedback::Nack],
+            }]
+        );
+    }
+
+    #[test]
+    fn rtp_parameters_from_media_lines_falls_back_to_static_cn() {
+        // No `a=rtpmap:13`: PT 13 is the classic static comfort-noise assignment, and the
+        // fallback must resolve all the way through `mime_type_from_encoding_name` rather than
+        // dead-ending on an "unknown" encoding name.
+        let lines = ["a=fmtp:13 ", "a=rtcp-fb:13 nack"];
+
+        let rtp_parameters =
+            rtp_parameters_from_media_lines(MediaKind::Audio, &lines).expect("Failed to parse");
+
+        assert_eq!(
+            rtp_parameters.codecs,
+            vec![RtpCodecParameters::Audio {
+                mime_type: MimeTypeAudio::CN,
+                payload_type: 13,
+                clock_rate: NonZeroU32::new(8000).unwrap(),
+                channels: NonZeroU8::new(1).unwrap(),
+                parameters: RtpCodecParametersParameters::new(),
+                rtcp_feedback: vec![RtcpFeedback::Nack],
+            }]
+        );
+    }
+
+    #[test]
+    fn answer_media_lines_from_offer_renders_red_fmtp_bare() {
+        let red = RtpCodecParameters::Audio {
+            mime_type: MimeTypeAudio::RED,
+            payload_type: 63,
+            clock_rate: NonZeroU32::new(48000).unwrap(),
+            channels: NonZeroU8::new(2).unwrap(),
+            parameters: RtpCodecParametersParameters::from([("63/63", 1u32.into())]),
+            rtcp_feedback: vec![],
+        };
+
+        let offered = RtpParameters {
+            mid: None,
+            codecs: vec![red.clone()],
+            header_extensions: vec![],
+            encodings: vec![],
+            rtcp: RtcpParameters::default(),
+        };
+        let supported = RtpParameters {
+            mid: None,
+            codecs: vec![red],
+            header_extensions: vec![],
+            encodings: vec![],
+            rtcp: RtcpParameters::default(),
+        };
+
+        let lines = answer_media_lines_from_offer(&offered, &supported);
+
+        assert!(lines.contains(&"a=fmtp:63 63/63".to_string()));
+    }
+
+    #[test]
+    fn media_lines_from_rtp_parameters_renders_red_fmtp_bare() {
+        // RED's fmtp carries a bare ordered PT list (`"63/63"`), not a `key=value` pair; this is
+        // the shape browsers use when offering RED for Opus, so getting it wrong breaks
+        // interop with them.
+        let rtp_parameters = RtpParameters {
+            mid: None,
+            codecs: vec![RtpCodecParameters::Audio {
+                mime_type: MimeTypeAudio::RED,
+                payload_type: 63,
+                clock_rate: NonZeroU32::new(48000).unwrap(),
+                channels: NonZeroU8::new(2).unwrap(),
+                parameters: RtpCodecParametersParameters::from([("63/63", 1u32.into())]),
+                rtcp_feedback: vec![],
+            }],
+            header_extensions: vec![],
+            encodings: vec![],
+            rtcp: RtcpParameters::default(),
+        };
+
+        let lines = media_lines_from_rtp_parameters(&rtp_parameters);
+
+        assert!(lines.contains(&"a=fmtp:63 63/63".to_string()));
+
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let round_tripped =
+            rtp_parameters_from_media_lines(MediaKind::Audio, &lines).expect("Failed to parse");
+
+        assert_eq!(round_tripped.codecs, rtp_parameters.codecs);
+    }
+
+    #[test]
+    fn media_lines_from_rtp_parameters_round_trips() {
+        let mut rtp_parameters = RtpParameters {
+            mid: Some("0".to_string()),
+            codecs: vec![RtpCodecParameters::Audio {
+                mime_type: MimeTypeAudio::Opus,
+                payload_type: 111,
+                clock_rate: NonZeroU32::new(48000).unwrap(),
+                channels: NonZeroU8::new(2).unwrap(),
+                parameters: RtpCodecParametersParameters::from([("useinbandfec", 1u32.into())]),
+                rtcp_feedback: vec![RtcpFeedback::TransportCC],
+            }],
+            header_extensions: vec![],
+            encodings: vec![RtpEncodingParameters {
+                ssrc: Some(1111),
+                rtx: Some(RtpEncodingParametersRtx { ssrc: 2222 }),
+                ..RtpEncodingParameters::default()
+            }],
+            rtcp: RtcpParameters {
+                cname: Some("test".to_string()),
+                ..RtcpParameters::default()
+            },
+        };
+
+        let lines = media_lines_from_rtp_parameters(&rtp_parameters);
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+
+        let round_tripped =
+            rtp_parameters_from_media_lines(MediaKind::Audio, &lines).expect("Failed to parse");
+
+        // max_bitrate/scalability_mode aren't round-tripped through SDP (and this test has no
+        // header extensions), so only compare the fields the export actually carries.
+        rtp_parameters.encodings[0].codec_payload_type.take();
+        assert_eq!(round_tripped.mid, rtp_parameters.mid);
+        assert_eq!(round_tripped.codecs, rtp_parameters.codecs);
+        assert_eq!(round_tripped.encodings[0].ssrc, rtp_parameters.encodings[0].ssrc);
+        assert_eq!(
+            round_tripped.encodings[0].rtx.as_ref().map(|rtx| rtx.ssrc),
+            rtp_parameters.encodings[0].rtx.as_ref().map(|rtx| rtx.ssrc)
+        );
+    }
+
+    #[test]
+    fn media_lines_from_rtp_parameters_rid_and_simulcast_are_always_send_direction() {
+        let rtp_parameters = RtpParameters {
+            mid: None,
+            codecs: vec![],
+            header_extensions: vec![],
+            encodings: vec![
+                RtpEncodingParameters {
+                    rid: Some("low".to_string()),
+                    ..RtpEncodingParameters::default()
+                },
+                RtpEncodingParameters {
+                    rid: Some("high".to_string()),
+                    ..RtpEncodingParameters::default()
+                },
+            ],
+            rtcp: RtcpParameters::default(),
+        };
+
+        let lines = media_lines_from_rtp_parameters(&rtp_parameters);
+
+        // This function only ever describes a local Producer's own RTP parameters, never a
+        // Consumer's, so "send" is the only direction that can ever be correct here.
+        assert!(lines.contains(&"a=rid:low send".to_string()));
+        assert!(lines.contains(&"a=rid:high send".to_string()));
+        assert!(lines.contains(&"a=simulcast:send low;high".to_string()));
+    }
+
+    #[test]
+    fn ice_candidate_sdp_attribute_round_trips() {
+        let candidate = IceCandidate {
+            foundation: "udpcandidate".to_string(),
+            priority: 1_076_302_079,
+            ip: "1.2.3.4".parse().unwrap(),
+            protocol: TransportProtocol::Udp,
+            port: 9,
+            r#type: IceCandidateType::Host,
+            tcp_type: None,
+        };
+
+        assert_eq!(
+            candidate.to_sdp_attribute(),
+            "udpcandidate 1 udp 1076302079 1.2.3.4 9 typ host"
+        );
+
+        assert_eq!(
+            IceCandidate::from_sdp_attribute(&candidate.to_sdp_attribute()),
+            Some(candidate)
+        );
+
+        let tcp_candidate = IceCandidate {
+            foundation: "tcpcandidate".to_string(),
+            priority: 1_019_216_383,
+            ip: "::1".parse().unwrap(),
+            protocol: TransportProtocol::Tcp,
+            port: 9,
+            r#type: IceCandidateType::Srflx,
+            tcp_type: Some(IceCandidateTcpType::Passive),
+        };
+
+        assert_eq!(
+            tcp_candidate.to_sdp_attribute(),
+            "tcpcandidate 1 tcp 1019216383 ::1 9 typ srflx tcptype passive"
+        );
+
+        assert_eq!(
+            IceCandidate::from_sdp_attribute(&tcp_candidate.to_sdp_attribute()),
+            Some(tcp_candidate)
+        );
+    }
+
+    #[test]
+    fn ice_parameters_to_sdp_lines() {
+        let ice_parameters = IceParameters {
+            username_fragment: "ufrag".to_string(),
+            password: "pwd".to_string(),
+            ice_lite: Some(true),
+        };
+
+        assert_eq!(
+            ice_parameters.to_sdp_lines(),
+            vec![
+                "a=ice-ufrag:ufrag".to_string(),
+                "a=ice-pwd:pwd".to_string(),
+                "a=ice-lite".to_string(),
+            ]
+        );
+    }
+}
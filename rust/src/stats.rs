@@ -0,0 +1,251 @@
+use crate::data_structures::{DtlsState, IceRole, IceState, RtpType, SctpState, TransportTuple};
+use serde::{Deserialize, Serialize};
+
+/// Statistics report for a Transport (WebRTC, Plain or Pipe), modeled on the W3C
+/// `RTCTransportStats` dictionary plus the extra RTP/RTX/probation counters mediasoup's worker
+/// reports alongside it.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransportStat {
+    pub timestamp: u64,
+    /// The id of the Transport this entry describes.
+    pub id: String,
+    pub sctp_state: Option<SctpState>,
+    pub bytes_received: u64,
+    pub recv_bitrate: u32,
+    pub bytes_sent: u64,
+    pub send_bitrate: u32,
+    pub rtp_bytes_received: u64,
+    pub rtp_recv_bitrate: u32,
+    pub rtp_bytes_sent: u64,
+    pub rtp_send_bitrate: u32,
+    pub rtx_bytes_received: u64,
+    pub rtx_recv_bitrate: u32,
+    pub rtx_bytes_sent: u64,
+    pub rtx_send_bitrate: u32,
+    pub probation_bytes_sent: u64,
+    pub probation_send_bitrate: u32,
+    pub available_outgoing_bitrate: Option<u32>,
+    pub available_incoming_bitrate: Option<u32>,
+    pub max_incoming_bitrate: Option<u32>,
+    pub ice_role: Option<IceRole>,
+    pub ice_state: Option<IceState>,
+    pub ice_selected_tuple: Option<TransportTuple>,
+    pub dtls_state: Option<DtlsState>,
+}
+
+/// Statistics report for a DataProducer/DataConsumer's SCTP association, the DataChannel
+/// counterpart of [`TransportStat`] with no RTP fields to speak of.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataTransportStat {
+    pub timestamp: u64,
+    /// The id of the DataProducer/DataConsumer this entry describes.
+    pub id: String,
+    pub label: String,
+    pub protocol: String,
+    pub messages_sent: u64,
+    pub bytes_sent: u64,
+    pub messages_received: u64,
+    pub bytes_received: u64,
+}
+
+/// Statistics report for a Producer's RTP stream, modeled on the W3C `RTCInboundRtpStreamStats`
+/// dictionary.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InboundRtpStat {
+    /// The id of the Producer this entry describes.
+    pub id: String,
+    pub ssrc: u32,
+    pub rid: Option<String>,
+    pub kind: String,
+    pub mime_type: String,
+    pub packets_received: u64,
+    pub bytes_received: u64,
+    pub jitter: u32,
+    pub packets_lost: u32,
+    pub packets_discarded: u64,
+    pub packets_retransmitted: u64,
+    pub packets_repaired: u64,
+    pub bitrate: u32,
+    pub score: u8,
+}
+
+/// Statistics report for a Consumer's RTP stream, modeled on the W3C `RTCOutboundRtpStreamStats`
+/// dictionary.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutboundRtpStat {
+    /// The id of the Consumer this entry describes.
+    pub id: String,
+    pub ssrc: u32,
+    pub rid: Option<String>,
+    pub kind: String,
+    pub mime_type: String,
+    pub packets_sent: u64,
+    pub bytes_sent: u64,
+    pub packets_discarded: u64,
+    pub packets_retransmitted: u64,
+    pub packets_repaired: u64,
+    pub round_trip_time: Option<u32>,
+    pub bitrate: u32,
+    pub score: u8,
+}
+
+/// One entry of a `transport.get_stats()`/`producer.get_stats()`/`consumer.get_stats()` reply,
+/// discriminated by the worker's `type` field the same way [`RtpType`] discriminates RTP stream
+/// direction.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "type")]
+pub enum Stats {
+    #[serde(rename = "transport")]
+    Transport(TransportStat),
+    #[serde(rename = "data-transport")]
+    DataTransport(DataTransportStat),
+    #[serde(rename = "inbound-rtp")]
+    InboundRtp(InboundRtpStat),
+    #[serde(rename = "outbound-rtp")]
+    OutboundRtp(OutboundRtpStat),
+}
+
+impl Stats {
+    /// The id of the object (Transport, DataProducer/DataConsumer, Producer or Consumer) this
+    /// entry describes, so callers can correlate it back to the handle that produced it.
+    pub fn id(&self) -> &str {
+        match self {
+            Stats::Transport(stat) => &stat.id,
+            Stats::DataTransport(stat) => &stat.id,
+            Stats::InboundRtp(stat) => &stat.id,
+            Stats::OutboundRtp(stat) => &stat.id,
+        }
+    }
+
+    /// The [`RtpType`] of this entry, if it describes an RTP stream rather than a transport.
+    pub fn rtp_type(&self) -> Option<RtpType> {
+        match self {
+            Stats::InboundRtp(_) => Some(RtpType::Inbound),
+            Stats::OutboundRtp(_) => Some(RtpType::Outbound),
+            Stats::Transport(_) | Stats::DataTransport(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserializes_transport_stat_from_worker_shaped_json() {
+        let json = r#"{
+            "type": "transport",
+            "transportId": "ignored-by-flattening",
+            "timestamp": 1111,
+            "id": "transport-1",
+            "sctpState": null,
+            "bytesReceived": 10,
+            "recvBitrate": 1,
+            "bytesSent": 20,
+            "sendBitrate": 2,
+            "rtpBytesReceived": 3,
+            "rtpRecvBitrate": 4,
+            "rtpBytesSent": 5,
+            "rtpSendBitrate": 6,
+            "rtxBytesReceived": 7,
+            "rtxRecvBitrate": 8,
+            "rtxBytesSent": 9,
+            "rtxSendBitrate": 10,
+            "probationBytesSent": 11,
+            "probationSendBitrate": 12,
+            "availableOutgoingBitrate": null,
+            "availableIncomingBitrate": null,
+            "maxIncomingBitrate": null,
+            "iceRole": null,
+            "iceState": null,
+            "iceSelectedTuple": null,
+            "dtlsState": null
+        }"#;
+
+        // Extra fields the worker sends that this struct doesn't model (e.g. `transportId`)
+        // must not break deserialization.
+        let stats: Stats = serde_json::from_str(json).expect("Failed to deserialize");
+
+        assert_eq!(stats.id(), "transport-1");
+        assert_eq!(stats.rtp_type(), None);
+        assert!(matches!(stats, Stats::Transport(_)));
+    }
+
+    #[test]
+    fn deserializes_data_transport_stat_by_type_tag() {
+        let json = r#"{
+            "type": "data-transport",
+            "timestamp": 1111,
+            "id": "data-producer-1",
+            "label": "chat",
+            "protocol": "sctp",
+            "messagesSent": 1,
+            "bytesSent": 2,
+            "messagesReceived": 3,
+            "bytesReceived": 4
+        }"#;
+
+        let stats: Stats = serde_json::from_str(json).expect("Failed to deserialize");
+
+        assert_eq!(stats.id(), "data-producer-1");
+        assert_eq!(stats.rtp_type(), None);
+        assert!(matches!(stats, Stats::DataTransport(_)));
+    }
+
+    #[test]
+    fn deserializes_inbound_rtp_stat_by_type_tag() {
+        let json = r#"{
+            "type": "inbound-rtp",
+            "id": "producer-1",
+            "ssrc": 1111,
+            "rid": null,
+            "kind": "audio",
+            "mimeType": "audio/opus",
+            "packetsReceived": 100,
+            "bytesReceived": 2000,
+            "jitter": 1,
+            "packetsLost": 0,
+            "packetsDiscarded": 0,
+            "packetsRetransmitted": 0,
+            "packetsRepaired": 0,
+            "bitrate": 64000,
+            "score": 10
+        }"#;
+
+        let stats: Stats = serde_json::from_str(json).expect("Failed to deserialize");
+
+        assert_eq!(stats.id(), "producer-1");
+        assert_eq!(stats.rtp_type(), Some(RtpType::Inbound));
+        assert!(matches!(stats, Stats::InboundRtp(_)));
+    }
+
+    #[test]
+    fn deserializes_outbound_rtp_stat_by_type_tag() {
+        let json = r#"{
+            "type": "outbound-rtp",
+            "id": "consumer-1",
+            "ssrc": 2222,
+            "rid": null,
+            "kind": "video",
+            "mimeType": "video/VP8",
+            "packetsSent": 100,
+            "bytesSent": 20000,
+            "packetsDiscarded": 0,
+            "packetsRetransmitted": 0,
+            "packetsRepaired": 0,
+            "roundTripTime": 20,
+            "bitrate": 500000,
+            "score": 9
+        }"#;
+
+        let stats: Stats = serde_json::from_str(json).expect("Failed to deserialize");
+
+        assert_eq!(stats.id(), "consumer-1");
+        assert_eq!(stats.rtp_type(), Some(RtpType::Outbound));
+        assert!(matches!(stats, Stats::OutboundRtp(_)));
+    }
+}